@@ -1,10 +1,10 @@
-use cornucopia::{conn::cornucopia_conn, CodegenSettings};
+use cornucopia::{conn::cornucopia_conn, container::ContainerSettings, CodegenSettings};
 use criterion::Criterion;
 
 fn bench(c: &mut Criterion) {
-    cornucopia::container::cleanup(false).ok();
-    cornucopia::container::setup(false).unwrap();
-    let client = &mut cornucopia_conn().unwrap();
+    let settings = ContainerSettings::default();
+    let container = cornucopia::container::setup(&settings).unwrap();
+    let client = &mut cornucopia_conn(&container).unwrap();
 
     cornucopia::load_schema(client, vec!["../codegen_test/schema.sql".into()]).unwrap();
     c.bench_function("codegen_sync", |b| {
@@ -16,6 +16,7 @@ fn bench(c: &mut Criterion) {
                 CodegenSettings {
                     is_async: false,
                     derive_ser: true,
+                    ..Default::default()
                 },
             )
             .unwrap()
@@ -30,12 +31,13 @@ fn bench(c: &mut Criterion) {
                 CodegenSettings {
                     is_async: true,
                     derive_ser: true,
+                    ..Default::default()
                 },
             )
             .unwrap()
         })
     });
-    cornucopia::container::cleanup(false).unwrap();
+    container.cleanup().unwrap();
 }
 criterion::criterion_group!(benches, bench);
 criterion::criterion_main!(benches);
@@ -351,6 +351,13 @@ pub mod queries {
                 Ok(it)
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// SELECT * FROM users;
+        /// ```
+        ///
+        /// </details>
         pub fn users() -> UsersStmt {
             UsersStmt(cornucopia_async::private::Stmt::new("SELECT * FROM users"))
         }
@@ -373,6 +380,13 @@ pub mod queries {
                 }
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// INSERT INTO users (name, hair_color) VALUES (:name, :hair_color);
+        /// ```
+        ///
+        /// </details>
         pub fn insert_user() -> InsertUserStmt {
             InsertUserStmt(cornucopia_async::private::Stmt::new(
                 "INSERT INTO users (name, hair_color) VALUES ($1, $2)",
@@ -424,6 +438,13 @@ pub mod queries {
                 Box::pin(self.bind(client, &params.name, &params.hair_color))
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// SELECT * FROM posts;
+        /// ```
+        ///
+        /// </details>
         pub fn posts() -> PostsStmt {
             PostsStmt(cornucopia_async::private::Stmt::new("SELECT * FROM posts"))
         }
@@ -447,6 +468,13 @@ pub mod queries {
                 }
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// SELECT * FROM posts WHERE user_id = ANY(:ids);
+        /// ```
+        ///
+        /// </details>
         pub fn post_by_user_ids() -> PostByUserIdsStmt {
             PostByUserIdsStmt(cornucopia_async::private::Stmt::new(
                 "SELECT * FROM posts WHERE user_id = ANY($1)",
@@ -473,6 +501,13 @@ pub mod queries {
                 }
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// SELECT * FROM comments;
+        /// ```
+        ///
+        /// </details>
         pub fn comments() -> CommentsStmt {
             CommentsStmt(cornucopia_async::private::Stmt::new(
                 "SELECT * FROM comments",
@@ -497,6 +532,13 @@ pub mod queries {
                 }
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// SELECT * FROM comments WHERE post_id = ANY(:ids);
+        /// ```
+        ///
+        /// </details>
         pub fn comments_by_post_id() -> CommentsByPostIdStmt {
             CommentsByPostIdStmt(cornucopia_async::private::Stmt::new(
                 "SELECT * FROM comments WHERE post_id = ANY($1)",
@@ -522,6 +564,13 @@ pub mod queries {
                 }
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// SELECT u.id as myuser_id, u.name, u.hair_color, p.id as post_id, p.user_id, p.title, p.body FROM users as u LEFT JOIN posts as p on u.id = p.user_id;
+        /// ```
+        ///
+        /// </details>
         pub fn select_complex() -> SelectComplexStmt {
             SelectComplexStmt(cornucopia_async :: private :: Stmt :: new("SELECT u.id as myuser_id, u.name, u.hair_color, p.id as post_id, p.user_id, p.title, p.body FROM users as u LEFT JOIN posts as p on u.id = p.user_id"))
         }
@@ -330,6 +330,13 @@ pub mod queries {
                 Ok(it)
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// SELECT * FROM users;
+        /// ```
+        ///
+        /// </details>
         pub fn users() -> UsersStmt {
             UsersStmt(cornucopia_sync::private::Stmt::new("SELECT * FROM users"))
         }
@@ -352,6 +359,13 @@ pub mod queries {
                 }
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// INSERT INTO users (name, hair_color) VALUES (:name, :hair_color);
+        /// ```
+        ///
+        /// </details>
         pub fn insert_user() -> InsertUserStmt {
             InsertUserStmt(cornucopia_sync::private::Stmt::new(
                 "INSERT INTO users (name, hair_color) VALUES ($1, $2)",
@@ -391,6 +405,13 @@ pub mod queries {
                 self.bind(client, &params.name, &params.hair_color)
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// SELECT * FROM posts;
+        /// ```
+        ///
+        /// </details>
         pub fn posts() -> PostsStmt {
             PostsStmt(cornucopia_sync::private::Stmt::new("SELECT * FROM posts"))
         }
@@ -414,6 +435,13 @@ pub mod queries {
                 }
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// SELECT * FROM posts WHERE user_id = ANY(:ids);
+        /// ```
+        ///
+        /// </details>
         pub fn post_by_user_ids() -> PostByUserIdsStmt {
             PostByUserIdsStmt(cornucopia_sync::private::Stmt::new(
                 "SELECT * FROM posts WHERE user_id = ANY($1)",
@@ -440,6 +468,13 @@ pub mod queries {
                 }
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// SELECT * FROM comments;
+        /// ```
+        ///
+        /// </details>
         pub fn comments() -> CommentsStmt {
             CommentsStmt(cornucopia_sync::private::Stmt::new(
                 "SELECT * FROM comments",
@@ -464,6 +499,13 @@ pub mod queries {
                 }
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// SELECT * FROM comments WHERE post_id = ANY(:ids);
+        /// ```
+        ///
+        /// </details>
         pub fn comments_by_post_id() -> CommentsByPostIdStmt {
             CommentsByPostIdStmt(cornucopia_sync::private::Stmt::new(
                 "SELECT * FROM comments WHERE post_id = ANY($1)",
@@ -489,6 +531,13 @@ pub mod queries {
                 }
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// SELECT u.id as myuser_id, u.name, u.hair_color, p.id as post_id, p.user_id, p.title, p.body FROM users as u LEFT JOIN posts as p on u.id = p.user_id;
+        /// ```
+        ///
+        /// </details>
         pub fn select_complex() -> SelectComplexStmt {
             SelectComplexStmt(cornucopia_sync :: private :: Stmt :: new("SELECT u.id as myuser_id, u.name, u.hair_color, p.id as post_id, p.user_id, p.title, p.body FROM users as u LEFT JOIN posts as p on u.id = p.user_id"))
         }
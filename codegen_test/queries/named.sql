@@ -1,4 +1,5 @@
 --: Id()
+-- A named row
 --: Named(price?)
 --: NamedParams(price?)
 
@@ -8,11 +9,16 @@ INSERT INTO named (name, price, show) VALUES (:name, :price, true) RETURNING id
 INSERT INTO named (price, name, show) VALUES (:price, :name, false) RETURNING id;
 --! named: Named
 SELECT * FROM named;
+-- Selects a named row by id.
+--
+-- Uses the declared `Named` row.
 --! named_by_id: Named
 SELECT * FROM named WHERE id = :id;
 
+-- A composite type
 --: named_composite(wow?,such_cool?)
 
+-- Inserts a named composite
 --! new_named_complex NamedComplexParams()
 INSERT INTO named_complex (named) VALUES (:named);
 --! named_complex
@@ -0,0 +1,2 @@
+--! select_nested_copy
+SELECT * FROM copy;
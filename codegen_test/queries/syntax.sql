@@ -26,6 +26,27 @@ INSERT INTO named (name, price, show) VALUES (:name, :price, false) RETURNING id
       --!       named_spaced            ParamsSpace  ()     :        RowSpace  () 
 INSERT INTO named (name, price, show) VALUES (:name, :price, false) RETURNING id;
 
+-- Inserts a named row, from code like:
+--
+--     let id = implicit_multiline().bind(client, &name, &price).one()?;
+--
+-- ```
+-- not rust either
+-- ```
+--! implicit_multiline (
+--   name?,
+--   price?,
+-- ) : (id?) #[
+--   deprecated = "use `implicit_compact` instead",
+-- ]
+INSERT INTO named (name, price, show) VALUES (:name, :price, false) RETURNING id;
+--!attribute_compact#[deprecated,]
+SELECT * FROM clone;
+--! attribute_passthrough #[timeout = "5s", cardinality = "one", deprecated]
+-- : an ordinary comment, not a continuation of the annotation
+-- #[nor is this]
+SELECT * FROM clone;
+
 --! tricky_sql
 INSERT INTO syntax ("trick:y", async, enum) VALUES ('this is not a bind_param\', :async, :enum);
 --! tricky_sql1
@@ -46,6 +67,25 @@ INSERT INTO syntax ("trick:y", async, enum) VALUES (e'this is ''not'' a \':bind_
 INSERT INTO syntax ("trick:y", async, enum) VALUES (E'this is \'not\' a \':bind_param\'', :async, :enum);
 --! tricky_sql10
 INSERT INTO syntax ("trick:y", async, enum) VALUES ('this is just a cast'::text, :async, :enum);
+--! tricky_sql11
+INSERT INTO syntax ("trick:y", async, enum) VALUES ('this is not a ;', :async, :enum);
+--! tricky_sql12
+INSERT INTO syntax ("trick:y", async, enum) VALUES ($$this is not a ; :bind_param$$, :async, :enum);
+--! tricky_sql13
+INSERT INTO syntax ("trick:y", async, enum) VALUES (E'this is not a \';', :async, :enum);
+--! tricky_sql14
+INSERT INTO syntax ("trick:y", async, enum) VALUES (
+    -- this is not a ; :bind_param
+    /* this is not a ; /* nested */ :bind_param */ 'commented', :async, :enum
+);
+/* Block comment
+   between queries; */
+--! tricky_sql15
+DO $$
+BEGIN
+    PERFORM 'this is not a ;';
+END
+$$;
 
 --! typeof
 SELECT * FROM syntax;
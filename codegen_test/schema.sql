@@ -22,6 +22,7 @@ CREATE TABLE copy (
 -- Domain
 
 CREATE DOMAIN domain_txt AS TEXT;
+COMMENT ON DOMAIN domain_txt IS 'Some text';
 CREATE DOMAIN domain_json AS JSON;
 CREATE DOMAIN domain_nb AS INT;
 CREATE DOMAIN domain_array AS domain_json[];
@@ -49,11 +50,13 @@ CREATE TABLE named (
     price FLOAT,
     show BOOLEAN NOT NULL
 );
+COMMENT ON COLUMN named.price IS 'The price, if any';
 
 CREATE TYPE named_composite AS (
     wow text,
     such_cool integer
 );
+COMMENT ON COLUMN named_composite.such_cool IS 'How cool it is';
 
 CREATE TABLE named_complex (
     named named_composite
@@ -162,12 +165,15 @@ CREATE TYPE spongebob_character AS enum (
     'Patrick',
     'Squidward'
 );
+COMMENT ON TYPE spongebob_character IS 'A character from Bikini Bottom';
 
 CREATE TYPE custom_composite AS (
     wow text,
     such_cool integer,
     nice spongebob_character
 );
+COMMENT ON TYPE custom_composite IS 'A composite with a custom type';
+COMMENT ON COLUMN custom_composite.nice IS 'Who is nice';
 
 CREATE DOMAIN my_domain AS TEXT CHECK (value ~ '^\w{5}$');
 CREATE DOMAIN custom_domain AS custom_composite[];
@@ -6,7 +6,8 @@
 #[allow(dead_code)]
 pub mod types {
     pub mod public {
-        #[derive(serde::Serialize, Debug, postgres_types :: FromSql, Clone, PartialEq)]
+        #[derive(serde::Serialize, Debug, postgres_types::FromSql, Clone, PartialEq)]
+        #[serde(rename_all = "camelCase")]
         #[postgres(name = "clone_composite")]
         pub struct CloneComposite {
             pub first: i32,
@@ -117,7 +118,8 @@ pub mod types {
                 postgres_types::__to_sql_checked(self, ty, out)
             }
         }
-        #[derive(serde::Serialize, Debug, postgres_types :: FromSql, Copy, Clone, PartialEq)]
+        #[derive(serde::Serialize, Debug, postgres_types::FromSql, Copy, Clone, PartialEq)]
+        #[serde(rename_all = "camelCase")]
         #[postgres(name = "copy_composite")]
         pub struct CopyComposite {
             pub first: i32,
@@ -186,9 +188,11 @@ pub mod types {
                 postgres_types::__to_sql_checked(self, ty, out)
             }
         }
-        #[derive(serde::Serialize, Debug, postgres_types :: FromSql, Clone, PartialEq)]
+        #[derive(serde::Serialize, Debug, postgres_types::FromSql, Clone, PartialEq)]
+        #[serde(rename_all = "camelCase")]
         #[postgres(name = "domain_composite")]
         pub struct DomainComposite {
+            /// Some text
             pub txt: String,
             pub json: serde_json::Value,
             pub nb: i32,
@@ -344,10 +348,13 @@ pub mod types {
                 postgres_types::__to_sql_checked(self, ty, out)
             }
         }
-        #[derive(serde::Serialize, Debug, postgres_types :: FromSql, Clone, PartialEq)]
+        /// A composite type
+        #[derive(serde::Serialize, Debug, postgres_types::FromSql, Clone, PartialEq)]
+        #[serde(rename_all = "camelCase")]
         #[postgres(name = "named_composite")]
         pub struct NamedComposite {
             pub wow: Option<String>,
+            /// How cool it is
             pub such_cool: Option<i32>,
         }
         #[derive(Debug)]
@@ -455,7 +462,8 @@ pub mod types {
                 postgres_types::__to_sql_checked(self, ty, out)
             }
         }
-        #[derive(serde::Serialize, Debug, postgres_types :: FromSql, Clone, PartialEq)]
+        #[derive(serde::Serialize, Debug, postgres_types::FromSql, Clone, PartialEq)]
+        #[serde(rename_all = "camelCase")]
         #[postgres(name = "nullity_composite")]
         pub struct NullityComposite {
             pub jsons: Option<Vec<Option<serde_json::Value>>>,
@@ -582,7 +590,9 @@ pub mod types {
                 postgres_types::__to_sql_checked(self, ty, out)
             }
         }
+        /// A character from Bikini Bottom
         #[derive(serde::Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+        #[serde(rename_all = "camelCase")]
         #[allow(non_camel_case_types)]
         pub enum SpongebobCharacter {
             Bob,
@@ -664,11 +674,14 @@ pub mod types {
                 }
             }
         }
-        #[derive(serde::Serialize, Debug, postgres_types :: FromSql, Clone, PartialEq)]
+        /// A composite with a custom type
+        #[derive(serde::Serialize, Debug, postgres_types::FromSql, Clone, PartialEq)]
+        #[serde(rename_all = "camelCase")]
         #[postgres(name = "custom_composite")]
         pub struct CustomComposite {
             pub wow: String,
             pub such_cool: i32,
+            /// Who is nice
             pub nice: super::super::types::public::SpongebobCharacter,
         }
         #[derive(Debug)]
@@ -797,7 +810,8 @@ pub mod types {
                 postgres_types::__to_sql_checked(self, ty, out)
             }
         }
-        #[derive(serde::Serialize, Debug, postgres_types :: FromSql, Clone, PartialEq)]
+        #[derive(serde::Serialize, Debug, postgres_types::FromSql, Clone, PartialEq)]
+        #[serde(rename_all = "camelCase")]
         #[postgres(name = "nightmare_composite")]
         pub struct NightmareComposite {
             pub custom: Vec<super::super::types::public::CustomComposite>,
@@ -946,7 +960,8 @@ pub mod types {
                 postgres_types::__to_sql_checked(self, ty, out)
             }
         }
-        #[derive(serde::Serialize, Debug, postgres_types :: FromSql, Copy, Clone, PartialEq)]
+        #[derive(serde::Serialize, Debug, postgres_types::FromSql, Copy, Clone, PartialEq)]
+        #[serde(rename_all = "camelCase")]
         #[postgres(name = "syntax_composite")]
         pub struct SyntaxComposite {
             pub r#async: i32,
@@ -1013,6 +1028,7 @@ pub mod types {
             }
         }
         #[derive(serde::Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+        #[serde(rename_all = "camelCase")]
         #[allow(non_camel_case_types)]
         pub enum SyntaxEnum {
             r#async,
@@ -1096,6 +1112,7 @@ pub mod types {
 #[allow(unused_imports)]
 #[allow(dead_code)]
 pub mod queries {
+    use super::types;
     pub mod copy {
         use cornucopia_async::GenericClient;
         use futures;
@@ -1211,6 +1228,13 @@ pub mod queries {
                 Ok(it)
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// INSERT INTO clone (composite) VALUES (:composite);
+        /// ```
+        ///
+        /// </details>
         pub fn insert_clone() -> InsertCloneStmt {
             InsertCloneStmt(cornucopia_async::private::Stmt::new(
                 "INSERT INTO clone (composite) VALUES ($1)",
@@ -1227,6 +1251,13 @@ pub mod queries {
                 client.execute(stmt, &[composite]).await
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// SELECT * FROM clone;
+        /// ```
+        ///
+        /// </details>
         pub fn select_clone() -> SelectCloneStmt {
             SelectCloneStmt(cornucopia_async::private::Stmt::new("SELECT * FROM clone"))
         }
@@ -1250,6 +1281,13 @@ pub mod queries {
                 }
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// INSERT INTO copy (composite) VALUES (:composite);
+        /// ```
+        ///
+        /// </details>
         pub fn insert_copy() -> InsertCopyStmt {
             InsertCopyStmt(cornucopia_async::private::Stmt::new(
                 "INSERT INTO copy (composite) VALUES ($1)",
@@ -1266,6 +1304,13 @@ pub mod queries {
                 client.execute(stmt, &[composite]).await
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// SELECT * FROM copy;
+        /// ```
+        ///
+        /// </details>
         pub fn select_copy() -> SelectCopyStmt {
             SelectCopyStmt(cornucopia_async::private::Stmt::new("SELECT * FROM copy"))
         }
@@ -1309,6 +1354,7 @@ pub mod queries {
             pub composite: Option<super::super::types::public::DomainCompositeParams<'a>>,
         }
         #[derive(serde::Serialize, Debug, Clone, PartialEq)]
+        #[serde(rename_all = "camelCase")]
         pub struct SelectNightmareDomain {
             pub txt: String,
             pub json: serde_json::Value,
@@ -1396,6 +1442,7 @@ pub mod queries {
             }
         }
         #[derive(serde::Serialize, Debug, Clone, PartialEq)]
+        #[serde(rename_all = "camelCase")]
         pub struct SelectNightmareDomainNull {
             pub txt: Option<String>,
             pub json: Option<serde_json::Value>,
@@ -1492,6 +1539,13 @@ pub mod queries {
                 Ok(it)
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// SELECT txt, json, nb, arr FROM nightmare_domain;
+        /// ```
+        ///
+        /// </details>
         pub fn select_nightmare_domain() -> SelectNightmareDomainStmt {
             SelectNightmareDomainStmt(cornucopia_async::private::Stmt::new(
                 "SELECT txt, json, nb, arr FROM nightmare_domain",
@@ -1517,6 +1571,13 @@ pub mod queries {
                 }
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// INSERT INTO nightmare_domain (txt, json, nb, arr, composite) VALUES (:txt, :json, :nb, :arr, :composite);
+        /// ```
+        ///
+        /// </details>
         pub fn insert_nightmare_domain() -> InsertNightmareDomainStmt {
             InsertNightmareDomainStmt(cornucopia_async :: private :: Stmt :: new("INSERT INTO nightmare_domain (txt, json, nb, arr, composite) VALUES ($1, $2, $3, $4, $5)"))
         }
@@ -1593,6 +1654,13 @@ pub mod queries {
                 ))
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// SELECT * FROM nightmare_domain;
+        /// ```
+        ///
+        /// </details>
         pub fn select_nightmare_domain_null() -> SelectNightmareDomainNullStmt {
             SelectNightmareDomainNullStmt(cornucopia_async::private::Stmt::new(
                 "SELECT * FROM nightmare_domain",
@@ -1634,6 +1702,7 @@ pub mod queries {
             pub named: super::super::types::public::NamedCompositeBorrowed<'a>,
         }
         #[derive(serde::Serialize, Debug, Clone, PartialEq, Copy)]
+        #[serde(rename_all = "camelCase")]
         pub struct Id {
             pub id: i32,
         }
@@ -1689,10 +1758,13 @@ pub mod queries {
                 Ok(it)
             }
         }
+        /// A named row
         #[derive(serde::Serialize, Debug, Clone, PartialEq)]
+        #[serde(rename_all = "camelCase")]
         pub struct Named {
             pub id: i32,
             pub name: String,
+            /// The price, if any
             pub price: Option<f64>,
             pub show: bool,
         }
@@ -1827,6 +1899,13 @@ pub mod queries {
                 Ok(it)
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// INSERT INTO named (name, price, show) VALUES (:name, :price, true) RETURNING id ;
+        /// ```
+        ///
+        /// </details>
         pub fn new_named_visible() -> NewNamedVisibleStmt {
             NewNamedVisibleStmt(cornucopia_async::private::Stmt::new(
                 "INSERT INTO named (name, price, show) VALUES ($1, $2, true) RETURNING id ",
@@ -1861,6 +1940,13 @@ pub mod queries {
                 self.bind(client, &params.name, &params.price)
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// INSERT INTO named (price, name, show) VALUES (:price, :name, false) RETURNING id;
+        /// ```
+        ///
+        /// </details>
         pub fn new_named_hidden() -> NewNamedHiddenStmt {
             NewNamedHiddenStmt(cornucopia_async::private::Stmt::new(
                 "INSERT INTO named (price, name, show) VALUES ($1, $2, false) RETURNING id",
@@ -1895,6 +1981,13 @@ pub mod queries {
                 self.bind(client, &params.price, &params.name)
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// SELECT * FROM named;
+        /// ```
+        ///
+        /// </details>
         pub fn named() -> NamedStmt {
             NamedStmt(cornucopia_async::private::Stmt::new("SELECT * FROM named"))
         }
@@ -1918,11 +2011,25 @@ pub mod queries {
                 }
             }
         }
+        /// Selects a named row by id.
+        ///
+        /// Uses the declared `Named` row.
+        ///
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// SELECT * FROM named WHERE id = :id;
+        /// ```
+        ///
+        /// </details>
         pub fn named_by_id() -> NamedByIdStmt {
             NamedByIdStmt(cornucopia_async::private::Stmt::new(
                 "SELECT * FROM named WHERE id = $1",
             ))
         }
+        /// Selects a named row by id.
+        ///
+        /// Uses the declared `Named` row.
         pub struct NamedByIdStmt(cornucopia_async::private::Stmt);
         impl NamedByIdStmt {
             pub fn bind<'a, C: GenericClient>(
@@ -1944,11 +2051,21 @@ pub mod queries {
                 }
             }
         }
+        /// Inserts a named composite
+        ///
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// INSERT INTO named_complex (named) VALUES (:named);
+        /// ```
+        ///
+        /// </details>
         pub fn new_named_complex() -> NewNamedComplexStmt {
             NewNamedComplexStmt(cornucopia_async::private::Stmt::new(
                 "INSERT INTO named_complex (named) VALUES ($1)",
             ))
         }
+        /// Inserts a named composite
         pub struct NewNamedComplexStmt(cornucopia_async::private::Stmt);
         impl NewNamedComplexStmt {
             pub async fn bind<'a, C: GenericClient>(
@@ -1984,6 +2101,13 @@ pub mod queries {
                 Box::pin(self.bind(client, &params.named))
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// SELECT * FROM named_complex;
+        /// ```
+        ///
+        /// </details>
         pub fn named_complex() -> NamedComplexStmt {
             NamedComplexStmt(cornucopia_async::private::Stmt::new(
                 "SELECT * FROM named_complex",
@@ -2026,6 +2150,7 @@ pub mod queries {
             pub composite: Option<super::super::types::public::NullityCompositeParams<'a>>,
         }
         #[derive(serde::Serialize, Debug, Clone, PartialEq)]
+        #[serde(rename_all = "camelCase")]
         pub struct Nullity {
             pub texts: Vec<Option<String>>,
             pub name: String,
@@ -2103,6 +2228,13 @@ pub mod queries {
                 Ok(it)
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// INSERT INTO nullity(texts, name, composite) VALUES (:texts, :name, :composite);
+        /// ```
+        ///
+        /// </details>
         pub fn new_nullity() -> NewNullityStmt {
             NewNullityStmt(cornucopia_async::private::Stmt::new(
                 "INSERT INTO nullity(texts, name, composite) VALUES ($1, $2, $3)",
@@ -2157,6 +2289,13 @@ pub mod queries {
                 Box::pin(self.bind(client, &params.texts, &params.name, &params.composite))
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// SELECT * FROM nullity;
+        /// ```
+        ///
+        /// </details>
         pub fn nullity() -> NullityStmt {
             NullityStmt(cornucopia_async::private::Stmt::new(
                 "SELECT * FROM nullity",
@@ -2200,6 +2339,7 @@ pub mod queries {
             pub a: i32,
         }
         #[derive(serde::Serialize, Debug, Clone, PartialEq)]
+        #[serde(rename_all = "camelCase")]
         pub struct SelectBook {
             pub name: String,
             pub author: Option<String>,
@@ -2272,6 +2412,7 @@ pub mod queries {
             }
         }
         #[derive(serde::Serialize, Debug, Clone, PartialEq)]
+        #[serde(rename_all = "camelCase")]
         pub struct FindBooks {
             pub name: String,
             pub author: Option<String>,
@@ -2340,6 +2481,13 @@ pub mod queries {
                 Ok(it)
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// INSERT INTO book (author, name) VALUES (:author, :name);
+        /// ```
+        ///
+        /// </details>
         pub fn insert_book() -> InsertBookStmt {
             InsertBookStmt(cornucopia_async::private::Stmt::new(
                 "INSERT INTO book (author, name) VALUES ($1, $2)",
@@ -2391,6 +2539,13 @@ pub mod queries {
                 Box::pin(self.bind(client, &params.author, &params.name))
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// SELECT * FROM book;
+        /// ```
+        ///
+        /// </details>
         pub fn select_book() -> SelectBookStmt {
             SelectBookStmt(cornucopia_async::private::Stmt::new("SELECT * FROM book"))
         }
@@ -2412,6 +2567,13 @@ pub mod queries {
                 }
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// SELECT * FROM book WHERE name = ANY (:title);
+        /// ```
+        ///
+        /// </details>
         pub fn find_books() -> FindBooksStmt {
             FindBooksStmt(cornucopia_async::private::Stmt::new(
                 "SELECT * FROM book WHERE name = ANY ($1)",
@@ -2441,6 +2603,13 @@ pub mod queries {
                 }
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// UPDATE book SET name = :name WHERE length(name) > 42 AND length(:name) < 42;
+        /// ```
+        ///
+        /// </details>
         pub fn params_use_twice() -> ParamsUseTwiceStmt {
             ParamsUseTwiceStmt(cornucopia_async::private::Stmt::new(
                 "UPDATE book SET name = $1 WHERE length(name) > 42 AND length($1) < 42",
@@ -2457,6 +2626,13 @@ pub mod queries {
                 client.execute(stmt, &[name]).await
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// UPDATE imaginary SET c=:c, a=:a, z=:a, r=:c;
+        /// ```
+        ///
+        /// </details>
         pub fn params_order() -> ParamsOrderStmt {
             ParamsOrderStmt(cornucopia_async::private::Stmt::new(
                 "UPDATE imaginary SET c=$1, a=$2, z=$2, r=$1",
@@ -2612,6 +2788,7 @@ pub mod queries {
             pub numeric_: T33,
         }
         #[derive(serde::Serialize, Debug, Clone, PartialEq)]
+        #[serde(rename_all = "camelCase")]
         pub struct Everything {
             pub bool_: bool,
             pub boolean_: bool,
@@ -2817,6 +2994,7 @@ pub mod queries {
             }
         }
         #[derive(serde::Serialize, Debug, Clone, PartialEq)]
+        #[serde(rename_all = "camelCase")]
         pub struct EverythingNull {
             pub bool_: Option<bool>,
             pub boolean_: Option<bool>,
@@ -3022,6 +3200,7 @@ pub mod queries {
             }
         }
         #[derive(serde::Serialize, Debug, Clone, PartialEq)]
+        #[serde(rename_all = "camelCase")]
         pub struct EverythingArray {
             pub bool_: Vec<bool>,
             pub boolean_: Vec<bool>,
@@ -3215,6 +3394,7 @@ pub mod queries {
             }
         }
         #[derive(serde::Serialize, Debug, Clone, PartialEq)]
+        #[serde(rename_all = "camelCase")]
         pub struct EverythingArrayNull {
             pub bool_: Option<Vec<bool>>,
             pub boolean_: Option<Vec<bool>>,
@@ -3476,6 +3656,16 @@ pub mod queries {
                 Ok(it)
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// SELECT
+        ///     *
+        /// FROM
+        ///     Everything;
+        /// ```
+        ///
+        /// </details>
         pub fn select_everything() -> SelectEverythingStmt {
             SelectEverythingStmt(cornucopia_async::private::Stmt::new(
                 "SELECT
@@ -3534,6 +3724,16 @@ FROM
                 }
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// SELECT
+        ///     *
+        /// FROM
+        ///     Everything;
+        /// ```
+        ///
+        /// </details>
         pub fn select_everything_null() -> SelectEverythingNullStmt {
             SelectEverythingNullStmt(cornucopia_async::private::Stmt::new(
                 "SELECT
@@ -3592,6 +3792,14 @@ FROM
                 }
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// INSERT INTO Everything (bool_, boolean_, char_, smallint_, int2_, smallserial_, serial2_, int_, int4_, serial_, serial4_, bingint_, int8_, bigserial_, serial8_, float4_, real_, float8_, double_precision_, text_, varchar_, bytea_, timestamp_, timestamp_without_time_zone_, timestamptz_, timestamp_with_time_zone_, date_, time_, json_, jsonb_, uuid_, inet_, macaddr_, numeric_)
+        ///     VALUES (:bool_, :boolean_, :char_, :smallint_, :int2_, :smallserial_, :serial2_, :int_, :int4_, :serial_, :serial4_, :bingint_, :int8_, :bigserial_, :serial8_, :float4_, :real_, :float8_, :double_precision_, :text_, :varchar_, :bytea_, :timestamp_, :timestamp_without_time_zone_, :timestamptz_, :timestamp_with_time_zone_, :date_, :time_, :json_, :jsonb_, :uuid_, :inet_, :macaddr_, :numeric_);
+        /// ```
+        ///
+        /// </details>
         pub fn insert_everything() -> InsertEverythingStmt {
             InsertEverythingStmt(cornucopia_async :: private :: Stmt :: new("INSERT INTO Everything (bool_, boolean_, char_, smallint_, int2_, smallserial_, serial2_, int_, int4_, serial_, serial4_, bingint_, int8_, bigserial_, serial8_, float4_, real_, float8_, double_precision_, text_, varchar_, bytea_, timestamp_, timestamp_without_time_zone_, timestamptz_, timestamp_with_time_zone_, date_, time_, json_, jsonb_, uuid_, inet_, macaddr_, numeric_)
     VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22, $23, $24, $25, $26, $27, $28, $29, $30, $31, $32, $33, $34)"))
@@ -3756,6 +3964,16 @@ FROM
                 ))
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// SELECT
+        ///     *
+        /// FROM
+        ///     EverythingArray;
+        /// ```
+        ///
+        /// </details>
         pub fn select_everything_array() -> SelectEverythingArrayStmt {
             SelectEverythingArrayStmt(cornucopia_async::private::Stmt::new(
                 "SELECT
@@ -3808,6 +4026,16 @@ FROM
                 }
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// SELECT
+        ///     *
+        /// FROM
+        ///     EverythingArray;
+        /// ```
+        ///
+        /// </details>
         pub fn select_everything_array_null() -> SelectEverythingArrayNullStmt {
             SelectEverythingArrayNullStmt(cornucopia_async::private::Stmt::new(
                 "SELECT
@@ -3860,6 +4088,14 @@ FROM
                 }
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// INSERT INTO EverythingArray (bool_, boolean_, char_, smallint_, int2_, int_, int4_, bingint_, int8_, float4_, real_, float8_, double_precision_, text_, varchar_, bytea_, timestamp_, timestamp_without_time_zone_, timestamptz_, timestamp_with_time_zone_, date_, time_, json_, jsonb_, uuid_, inet_, macaddr_, numeric_)
+        ///     VALUES (:bool_, :boolean_, :char_, :smallint_, :int2_, :int_, :int4_, :bingint_, :int8_, :float4_, :real_, :float8_, :double_precision_, :text_, :varchar_, :bytea_, :timestamp_, :timestamp_without_time_zone_, :timestamptz_, :timestamp_with_time_zone_, :date_, :time_, :json_, :jsonb_, :uuid_, :inet_, :macaddr_, :numeric_);
+        /// ```
+        ///
+        /// </details>
         pub fn insert_everything_array() -> InsertEverythingArrayStmt {
             InsertEverythingArrayStmt(cornucopia_async :: private :: Stmt :: new("INSERT INTO EverythingArray (bool_, boolean_, char_, smallint_, int2_, int_, int4_, bingint_, int8_, float4_, real_, float8_, double_precision_, text_, varchar_, bytea_, timestamp_, timestamp_without_time_zone_, timestamptz_, timestamp_with_time_zone_, date_, time_, json_, jsonb_, uuid_, inet_, macaddr_, numeric_)
     VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22, $23, $24, $25, $26, $27, $28)"))
@@ -4130,6 +4366,16 @@ FROM
                 ))
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// SELECT
+        ///     *
+        /// FROM
+        ///     nightmare;
+        /// ```
+        ///
+        /// </details>
         pub fn select_nightmare() -> SelectNightmareStmt {
             SelectNightmareStmt(cornucopia_async::private::Stmt::new(
                 "SELECT
@@ -4158,6 +4404,14 @@ FROM
                 }
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// INSERT INTO nightmare (composite)
+        ///     VALUES (:composite);
+        /// ```
+        ///
+        /// </details>
         pub fn insert_nightmare() -> InsertNightmareStmt {
             InsertNightmareStmt(cornucopia_async::private::Stmt::new(
                 "INSERT INTO nightmare (composite)
@@ -4190,7 +4444,7 @@ FROM
             pub name: Option<T1>,
             pub price: Option<f64>,
         }
-        #[derive(Debug)]
+        #[derive(Debug, Clone)]
         pub struct Params<T1: cornucopia_async::StringSql> {
             pub name: T1,
             pub price: f64,
@@ -4200,6 +4454,20 @@ FROM
             pub name: T1,
             pub price: f64,
         }
+        /// Inserts a named row, from code like:
+        ///
+        /// ```text
+        /// let id = implicit_multiline().bind(client, &name, &price).one()?;
+        /// ```
+        ///
+        /// ```text
+        /// not rust either
+        /// ```
+        #[derive(Debug)]
+        pub struct ImplicitMultilineParams<T1: cornucopia_async::StringSql> {
+            pub name: Option<T1>,
+            pub price: Option<f64>,
+        }
         #[derive(Clone, Copy, Debug)]
         pub struct TrickySqlParams {
             pub r#async: super::super::types::public::SyntaxComposite,
@@ -4250,6 +4518,26 @@ FROM
             pub r#async: super::super::types::public::SyntaxComposite,
             pub r#enum: super::super::types::public::SyntaxEnum,
         }
+        #[derive(Clone, Copy, Debug)]
+        pub struct TrickySql11Params {
+            pub r#async: super::super::types::public::SyntaxComposite,
+            pub r#enum: super::super::types::public::SyntaxEnum,
+        }
+        #[derive(Clone, Copy, Debug)]
+        pub struct TrickySql12Params {
+            pub r#async: super::super::types::public::SyntaxComposite,
+            pub r#enum: super::super::types::public::SyntaxEnum,
+        }
+        #[derive(Clone, Copy, Debug)]
+        pub struct TrickySql13Params {
+            pub r#async: super::super::types::public::SyntaxComposite,
+            pub r#enum: super::super::types::public::SyntaxEnum,
+        }
+        #[derive(Clone, Copy, Debug)]
+        pub struct TrickySql14Params {
+            pub r#async: super::super::types::public::SyntaxComposite,
+            pub r#enum: super::super::types::public::SyntaxEnum,
+        }
         pub struct SuperSuperTypesPublicCloneCompositeQuery<'a, C: GenericClient, T, const N: usize> {
             client: &'a C,
             params: [&'a (dyn postgres_types::ToSql + Sync); N],
@@ -4359,6 +4647,7 @@ FROM
             }
         }
         #[derive(serde::Serialize, Debug, Clone, PartialEq, Copy)]
+        #[serde(rename_all = "camelCase")]
         pub struct Row {
             pub id: i32,
         }
@@ -4415,6 +4704,7 @@ FROM
             }
         }
         #[derive(serde::Serialize, Debug, Clone, PartialEq, Copy)]
+        #[serde(rename_all = "camelCase")]
         pub struct RowSpace {
             pub id: i32,
         }
@@ -4471,6 +4761,7 @@ FROM
             }
         }
         #[derive(serde::Serialize, Debug, Clone, PartialEq)]
+        #[serde(rename_all = "camelCase")]
         pub struct Typeof {
             pub trick_y: String,
             pub r#async: super::super::types::public::SyntaxComposite,
@@ -4548,6 +4839,13 @@ FROM
                 Ok(it)
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// SELECT * FROM clone;
+        /// ```
+        ///
+        /// </details>
         pub fn select_compact() -> SelectCompactStmt {
             SelectCompactStmt(cornucopia_async::private::Stmt::new("SELECT * FROM clone"))
         }
@@ -4571,6 +4869,13 @@ FROM
                 }
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// SELECT * FROM clone ;
+        /// ```
+        ///
+        /// </details>
         pub fn select_spaced() -> SelectSpacedStmt {
             SelectSpacedStmt(cornucopia_async::private::Stmt::new(
                 "      SELECT * FROM clone ",
@@ -4596,6 +4901,13 @@ FROM
                 }
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// INSERT INTO named (name, price, show) VALUES (:name, :price, false) RETURNING id;
+        /// ```
+        ///
+        /// </details>
         pub fn implicit_compact() -> ImplicitCompactStmt {
             ImplicitCompactStmt(cornucopia_async::private::Stmt::new(
                 "INSERT INTO named (name, price, show) VALUES ($1, $2, false) RETURNING id",
@@ -4634,6 +4946,13 @@ FROM
                 self.bind(client, &params.name, &params.price)
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// INSERT INTO named (name, price, show) VALUES (:name, :price, false) RETURNING id;
+        /// ```
+        ///
+        /// </details>
         pub fn implicit_spaced() -> ImplicitSpacedStmt {
             ImplicitSpacedStmt(cornucopia_async::private::Stmt::new(
                 "INSERT INTO named (name, price, show) VALUES ($1, $2, false) RETURNING id",
@@ -4672,6 +4991,13 @@ FROM
                 self.bind(client, &params.name, &params.price)
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// INSERT INTO named (name, price, show) VALUES (:name, :price, false) RETURNING id;
+        /// ```
+        ///
+        /// </details>
         pub fn named_compact() -> NamedCompactStmt {
             NamedCompactStmt(cornucopia_async::private::Stmt::new(
                 "INSERT INTO named (name, price, show) VALUES ($1, $2, false) RETURNING id",
@@ -4706,6 +5032,13 @@ FROM
                 self.bind(client, &params.name, &params.price)
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// INSERT INTO named (name, price, show) VALUES (:name, :price, false) RETURNING id;
+        /// ```
+        ///
+        /// </details>
         pub fn named_spaced() -> NamedSpacedStmt {
             NamedSpacedStmt(cornucopia_async::private::Stmt::new(
                 "INSERT INTO named (name, price, show) VALUES ($1, $2, false) RETURNING id",
@@ -4740,8 +5073,152 @@ FROM
                 self.bind(client, &params.name, &params.price)
             }
         }
+        /// Inserts a named row, from code like:
+        ///
+        /// ```text
+        /// let id = implicit_multiline().bind(client, &name, &price).one()?;
+        /// ```
+        ///
+        /// ```text
+        /// not rust either
+        /// ```
+        ///
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// INSERT INTO named (name, price, show) VALUES (:name, :price, false) RETURNING id;
+        /// ```
+        ///
+        /// </details>
+        #[deprecated = "use `implicit_compact` instead"]
+        pub fn implicit_multiline() -> ImplicitMultilineStmt {
+            ImplicitMultilineStmt(cornucopia_async::private::Stmt::new(
+                "INSERT INTO named (name, price, show) VALUES ($1, $2, false) RETURNING id",
+            ))
+        }
+        /// Inserts a named row, from code like:
+        ///
+        /// ```text
+        /// let id = implicit_multiline().bind(client, &name, &price).one()?;
+        /// ```
+        ///
+        /// ```text
+        /// not rust either
+        /// ```
+        pub struct ImplicitMultilineStmt(cornucopia_async::private::Stmt);
+        impl ImplicitMultilineStmt {
+            pub fn bind<'a, C: GenericClient, T1: cornucopia_async::StringSql>(
+                &'a mut self,
+                client: &'a C,
+                name: &'a Option<T1>,
+                price: &'a Option<f64>,
+            ) -> Optioni32Query<'a, C, Option<i32>, 2> {
+                Optioni32Query {
+                    client,
+                    params: [name, price],
+                    stmt: &mut self.0,
+                    extractor: |row| row.get(0),
+                    mapper: |it| it,
+                }
+            }
+        }
+        impl<'a, C: GenericClient, T1: cornucopia_async::StringSql>
+            cornucopia_async::Params<
+                'a,
+                ImplicitMultilineParams<T1>,
+                Optioni32Query<'a, C, Option<i32>, 2>,
+                C,
+            > for ImplicitMultilineStmt
+        {
+            fn params(
+                &'a mut self,
+                client: &'a C,
+                params: &'a ImplicitMultilineParams<T1>,
+            ) -> Optioni32Query<'a, C, Option<i32>, 2> {
+                self.bind(client, &params.name, &params.price)
+            }
+        }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// SELECT * FROM clone;
+        /// ```
+        ///
+        /// </details>
+        #[deprecated]
+        pub fn attribute_compact() -> AttributeCompactStmt {
+            AttributeCompactStmt(cornucopia_async::private::Stmt::new("SELECT * FROM clone"))
+        }
+        pub struct AttributeCompactStmt(cornucopia_async::private::Stmt);
+        impl AttributeCompactStmt {
+            pub fn bind<'a, C: GenericClient>(
+                &'a mut self,
+                client: &'a C,
+            ) -> SuperSuperTypesPublicCloneCompositeQuery<
+                'a,
+                C,
+                super::super::types::public::CloneComposite,
+                0,
+            > {
+                SuperSuperTypesPublicCloneCompositeQuery {
+                    client,
+                    params: [],
+                    stmt: &mut self.0,
+                    extractor: |row| row.get(0),
+                    mapper: |it| it.into(),
+                }
+            }
+        }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// -- : an ordinary comment, not a continuation of the annotation
+        /// -- #[nor is this]
+        /// SELECT * FROM clone;
+        /// ```
+        ///
+        /// </details>
+        #[deprecated]
+        pub fn attribute_passthrough() -> AttributePassthroughStmt {
+            AttributePassthroughStmt(cornucopia_async::private::Stmt::new(
+                "-- : an ordinary comment, not a continuation of the annotation
+-- #[nor is this]
+SELECT * FROM clone",
+            ))
+        }
+        pub struct AttributePassthroughStmt(cornucopia_async::private::Stmt);
+        impl AttributePassthroughStmt {
+            pub fn bind<'a, C: GenericClient>(
+                &'a mut self,
+                client: &'a C,
+            ) -> SuperSuperTypesPublicCloneCompositeQuery<
+                'a,
+                C,
+                super::super::types::public::CloneComposite,
+                0,
+            > {
+                SuperSuperTypesPublicCloneCompositeQuery {
+                    client,
+                    params: [],
+                    stmt: &mut self.0,
+                    extractor: |row| row.get(0),
+                    mapper: |it| it.into(),
+                }
+            }
+        }
+        impl AttributePassthroughStmt {
+            pub const ATTRIBUTES: &'static [(&'static str, Option<&'static str>)] =
+                &[("timeout", Some("5s")), ("cardinality", Some("one"))];
+        }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// INSERT INTO syntax ("trick:y", async, enum) VALUES ('this is not a bind_param\', :async, :enum);
+        /// ```
+        ///
+        /// </details>
         pub fn tricky_sql() -> TrickySqlStmt {
-            TrickySqlStmt(cornucopia_async :: private :: Stmt :: new("INSERT INTO syntax (\"trick:y\", async, enum) VALUES ('this is not a bind_param\', $1, $2)"))
+            TrickySqlStmt(cornucopia_async :: private :: Stmt :: new("INSERT INTO syntax (\"trick:y\", async, enum) VALUES ('this is not a bind_param\\', $1, $2)"))
         }
         pub struct TrickySqlStmt(cornucopia_async::private::Stmt);
         impl TrickySqlStmt {
@@ -4779,6 +5256,13 @@ FROM
                 Box::pin(self.bind(client, &params.r#async, &params.r#enum))
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// INSERT INTO syntax ("trick:y", async, enum) VALUES ('this is not a :bind_param', :async, :enum);
+        /// ```
+        ///
+        /// </details>
         pub fn tricky_sql1() -> TrickySql1Stmt {
             TrickySql1Stmt(cornucopia_async :: private :: Stmt :: new("INSERT INTO syntax (\"trick:y\", async, enum) VALUES ('this is not a :bind_param', $1, $2)"))
         }
@@ -4818,6 +5302,13 @@ FROM
                 Box::pin(self.bind(client, &params.r#async, &params.r#enum))
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// INSERT INTO syntax ("trick:y", async, enum) VALUES ('this is not a '':bind_param''', :async, :enum);
+        /// ```
+        ///
+        /// </details>
         pub fn tricky_sql2() -> TrickySql2Stmt {
             TrickySql2Stmt(cornucopia_async :: private :: Stmt :: new("INSERT INTO syntax (\"trick:y\", async, enum) VALUES ('this is not a '':bind_param''', $1, $2)"))
         }
@@ -4857,6 +5348,13 @@ FROM
                 Box::pin(self.bind(client, &params.r#async, &params.r#enum))
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// INSERT INTO syntax ("trick:y", async, enum)  VALUES ($$this is not a :bind_param$$, :async, :enum);
+        /// ```
+        ///
+        /// </details>
         pub fn tricky_sql3() -> TrickySql3Stmt {
             TrickySql3Stmt(cornucopia_async :: private :: Stmt :: new("INSERT INTO syntax (\"trick:y\", async, enum)  VALUES ($$this is not a :bind_param$$, $1, $2)"))
         }
@@ -4896,6 +5394,13 @@ FROM
                 Box::pin(self.bind(client, &params.r#async, &params.r#enum))
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// INSERT INTO syntax ("trick:y", async, enum) VALUES ($tag$this is not a :bind_param$tag$, :async, :enum);
+        /// ```
+        ///
+        /// </details>
         pub fn tricky_sql4() -> TrickySql4Stmt {
             TrickySql4Stmt(cornucopia_async :: private :: Stmt :: new("INSERT INTO syntax (\"trick:y\", async, enum) VALUES ($tag$this is not a :bind_param$tag$, $1, $2)"))
         }
@@ -4935,6 +5440,13 @@ FROM
                 Box::pin(self.bind(client, &params.r#async, &params.r#enum))
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// INSERT INTO syntax ("trick:y", async, enum) VALUES (e'this is not a '':bind_param''', :async, :enum);
+        /// ```
+        ///
+        /// </details>
         pub fn tricky_sql6() -> TrickySql6Stmt {
             TrickySql6Stmt(cornucopia_async :: private :: Stmt :: new("INSERT INTO syntax (\"trick:y\", async, enum) VALUES (e'this is not a '':bind_param''', $1, $2)"))
         }
@@ -4974,8 +5486,15 @@ FROM
                 Box::pin(self.bind(client, &params.r#async, &params.r#enum))
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// INSERT INTO syntax ("trick:y", async, enum) VALUES (E'this is not a \':bind_param\'', :async, :enum);
+        /// ```
+        ///
+        /// </details>
         pub fn tricky_sql7() -> TrickySql7Stmt {
-            TrickySql7Stmt(cornucopia_async :: private :: Stmt :: new("INSERT INTO syntax (\"trick:y\", async, enum) VALUES (E'this is not a \':bind_param\'', $1, $2)"))
+            TrickySql7Stmt(cornucopia_async :: private :: Stmt :: new("INSERT INTO syntax (\"trick:y\", async, enum) VALUES (E'this is not a \\':bind_param\\'', $1, $2)"))
         }
         pub struct TrickySql7Stmt(cornucopia_async::private::Stmt);
         impl TrickySql7Stmt {
@@ -5013,8 +5532,15 @@ FROM
                 Box::pin(self.bind(client, &params.r#async, &params.r#enum))
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// INSERT INTO syntax ("trick:y", async, enum) VALUES (e'this is ''not'' a \':bind_param\'', :async, :enum);
+        /// ```
+        ///
+        /// </details>
         pub fn tricky_sql8() -> TrickySql8Stmt {
-            TrickySql8Stmt(cornucopia_async :: private :: Stmt :: new("INSERT INTO syntax (\"trick:y\", async, enum) VALUES (e'this is ''not'' a \':bind_param\'', $1, $2)"))
+            TrickySql8Stmt(cornucopia_async :: private :: Stmt :: new("INSERT INTO syntax (\"trick:y\", async, enum) VALUES (e'this is ''not'' a \\':bind_param\\'', $1, $2)"))
         }
         pub struct TrickySql8Stmt(cornucopia_async::private::Stmt);
         impl TrickySql8Stmt {
@@ -5052,8 +5578,15 @@ FROM
                 Box::pin(self.bind(client, &params.r#async, &params.r#enum))
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// INSERT INTO syntax ("trick:y", async, enum) VALUES (E'this is \'not\' a \':bind_param\'', :async, :enum);
+        /// ```
+        ///
+        /// </details>
         pub fn tricky_sql9() -> TrickySql9Stmt {
-            TrickySql9Stmt(cornucopia_async :: private :: Stmt :: new("INSERT INTO syntax (\"trick:y\", async, enum) VALUES (E'this is \'not\' a \':bind_param\'', $1, $2)"))
+            TrickySql9Stmt(cornucopia_async :: private :: Stmt :: new("INSERT INTO syntax (\"trick:y\", async, enum) VALUES (E'this is \\'not\\' a \\':bind_param\\'', $1, $2)"))
         }
         pub struct TrickySql9Stmt(cornucopia_async::private::Stmt);
         impl TrickySql9Stmt {
@@ -5091,6 +5624,13 @@ FROM
                 Box::pin(self.bind(client, &params.r#async, &params.r#enum))
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// INSERT INTO syntax ("trick:y", async, enum) VALUES ('this is just a cast'::text, :async, :enum);
+        /// ```
+        ///
+        /// </details>
         pub fn tricky_sql10() -> TrickySql10Stmt {
             TrickySql10Stmt(cornucopia_async :: private :: Stmt :: new("INSERT INTO syntax (\"trick:y\", async, enum) VALUES ('this is just a cast'::text, $1, $2)"))
         }
@@ -5130,6 +5670,237 @@ FROM
                 Box::pin(self.bind(client, &params.r#async, &params.r#enum))
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// INSERT INTO syntax ("trick:y", async, enum) VALUES ('this is not a ;', :async, :enum);
+        /// ```
+        ///
+        /// </details>
+        pub fn tricky_sql11() -> TrickySql11Stmt {
+            TrickySql11Stmt(cornucopia_async::private::Stmt::new(
+                "INSERT INTO syntax (\"trick:y\", async, enum) VALUES ('this is not a ;', $1, $2)",
+            ))
+        }
+        pub struct TrickySql11Stmt(cornucopia_async::private::Stmt);
+        impl TrickySql11Stmt {
+            pub async fn bind<'a, C: GenericClient>(
+                &'a mut self,
+                client: &'a C,
+                r#async: &'a super::super::types::public::SyntaxComposite,
+                r#enum: &'a super::super::types::public::SyntaxEnum,
+            ) -> Result<u64, tokio_postgres::Error> {
+                let stmt = self.0.prepare(client).await?;
+                client.execute(stmt, &[r#async, r#enum]).await
+            }
+        }
+        impl<'a, C: GenericClient + Send + Sync>
+            cornucopia_async::Params<
+                'a,
+                TrickySql11Params,
+                std::pin::Pin<
+                    Box<
+                        dyn futures::Future<Output = Result<u64, tokio_postgres::Error>>
+                            + Send
+                            + 'a,
+                    >,
+                >,
+                C,
+            > for TrickySql11Stmt
+        {
+            fn params(
+                &'a mut self,
+                client: &'a C,
+                params: &'a TrickySql11Params,
+            ) -> std::pin::Pin<
+                Box<dyn futures::Future<Output = Result<u64, tokio_postgres::Error>> + Send + 'a>,
+            > {
+                Box::pin(self.bind(client, &params.r#async, &params.r#enum))
+            }
+        }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// INSERT INTO syntax ("trick:y", async, enum) VALUES ($$this is not a ; :bind_param$$, :async, :enum);
+        /// ```
+        ///
+        /// </details>
+        pub fn tricky_sql12() -> TrickySql12Stmt {
+            TrickySql12Stmt(cornucopia_async :: private :: Stmt :: new("INSERT INTO syntax (\"trick:y\", async, enum) VALUES ($$this is not a ; :bind_param$$, $1, $2)"))
+        }
+        pub struct TrickySql12Stmt(cornucopia_async::private::Stmt);
+        impl TrickySql12Stmt {
+            pub async fn bind<'a, C: GenericClient>(
+                &'a mut self,
+                client: &'a C,
+                r#async: &'a super::super::types::public::SyntaxComposite,
+                r#enum: &'a super::super::types::public::SyntaxEnum,
+            ) -> Result<u64, tokio_postgres::Error> {
+                let stmt = self.0.prepare(client).await?;
+                client.execute(stmt, &[r#async, r#enum]).await
+            }
+        }
+        impl<'a, C: GenericClient + Send + Sync>
+            cornucopia_async::Params<
+                'a,
+                TrickySql12Params,
+                std::pin::Pin<
+                    Box<
+                        dyn futures::Future<Output = Result<u64, tokio_postgres::Error>>
+                            + Send
+                            + 'a,
+                    >,
+                >,
+                C,
+            > for TrickySql12Stmt
+        {
+            fn params(
+                &'a mut self,
+                client: &'a C,
+                params: &'a TrickySql12Params,
+            ) -> std::pin::Pin<
+                Box<dyn futures::Future<Output = Result<u64, tokio_postgres::Error>> + Send + 'a>,
+            > {
+                Box::pin(self.bind(client, &params.r#async, &params.r#enum))
+            }
+        }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// INSERT INTO syntax ("trick:y", async, enum) VALUES (E'this is not a \';', :async, :enum);
+        /// ```
+        ///
+        /// </details>
+        pub fn tricky_sql13() -> TrickySql13Stmt {
+            TrickySql13Stmt(cornucopia_async :: private :: Stmt :: new("INSERT INTO syntax (\"trick:y\", async, enum) VALUES (E'this is not a \\';', $1, $2)"))
+        }
+        pub struct TrickySql13Stmt(cornucopia_async::private::Stmt);
+        impl TrickySql13Stmt {
+            pub async fn bind<'a, C: GenericClient>(
+                &'a mut self,
+                client: &'a C,
+                r#async: &'a super::super::types::public::SyntaxComposite,
+                r#enum: &'a super::super::types::public::SyntaxEnum,
+            ) -> Result<u64, tokio_postgres::Error> {
+                let stmt = self.0.prepare(client).await?;
+                client.execute(stmt, &[r#async, r#enum]).await
+            }
+        }
+        impl<'a, C: GenericClient + Send + Sync>
+            cornucopia_async::Params<
+                'a,
+                TrickySql13Params,
+                std::pin::Pin<
+                    Box<
+                        dyn futures::Future<Output = Result<u64, tokio_postgres::Error>>
+                            + Send
+                            + 'a,
+                    >,
+                >,
+                C,
+            > for TrickySql13Stmt
+        {
+            fn params(
+                &'a mut self,
+                client: &'a C,
+                params: &'a TrickySql13Params,
+            ) -> std::pin::Pin<
+                Box<dyn futures::Future<Output = Result<u64, tokio_postgres::Error>> + Send + 'a>,
+            > {
+                Box::pin(self.bind(client, &params.r#async, &params.r#enum))
+            }
+        }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// INSERT INTO syntax ("trick:y", async, enum) VALUES (
+        ///     -- this is not a ; :bind_param
+        ///     /* this is not a ; /* nested */ :bind_param */ 'commented', :async, :enum
+        /// );
+        /// ```
+        ///
+        /// </details>
+        pub fn tricky_sql14() -> TrickySql14Stmt {
+            TrickySql14Stmt(cornucopia_async::private::Stmt::new(
+                "INSERT INTO syntax (\"trick:y\", async, enum) VALUES (
+    -- this is not a ; :bind_param
+    /* this is not a ; /* nested */ :bind_param */ 'commented', $1, $2
+)",
+            ))
+        }
+        pub struct TrickySql14Stmt(cornucopia_async::private::Stmt);
+        impl TrickySql14Stmt {
+            pub async fn bind<'a, C: GenericClient>(
+                &'a mut self,
+                client: &'a C,
+                r#async: &'a super::super::types::public::SyntaxComposite,
+                r#enum: &'a super::super::types::public::SyntaxEnum,
+            ) -> Result<u64, tokio_postgres::Error> {
+                let stmt = self.0.prepare(client).await?;
+                client.execute(stmt, &[r#async, r#enum]).await
+            }
+        }
+        impl<'a, C: GenericClient + Send + Sync>
+            cornucopia_async::Params<
+                'a,
+                TrickySql14Params,
+                std::pin::Pin<
+                    Box<
+                        dyn futures::Future<Output = Result<u64, tokio_postgres::Error>>
+                            + Send
+                            + 'a,
+                    >,
+                >,
+                C,
+            > for TrickySql14Stmt
+        {
+            fn params(
+                &'a mut self,
+                client: &'a C,
+                params: &'a TrickySql14Params,
+            ) -> std::pin::Pin<
+                Box<dyn futures::Future<Output = Result<u64, tokio_postgres::Error>> + Send + 'a>,
+            > {
+                Box::pin(self.bind(client, &params.r#async, &params.r#enum))
+            }
+        }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// DO $$
+        /// BEGIN
+        ///     PERFORM 'this is not a ;';
+        /// END
+        /// $$;
+        /// ```
+        ///
+        /// </details>
+        pub fn tricky_sql15() -> TrickySql15Stmt {
+            TrickySql15Stmt(cornucopia_async::private::Stmt::new(
+                "DO $$
+BEGIN
+    PERFORM 'this is not a ;';
+END
+$$",
+            ))
+        }
+        pub struct TrickySql15Stmt(cornucopia_async::private::Stmt);
+        impl TrickySql15Stmt {
+            pub async fn bind<'a, C: GenericClient>(
+                &'a mut self,
+                client: &'a C,
+            ) -> Result<u64, tokio_postgres::Error> {
+                let stmt = self.0.prepare(client).await?;
+                client.execute(stmt, &[]).await
+            }
+        }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// SELECT * FROM syntax;
+        /// ```
+        ///
+        /// </details>
         pub fn r#typeof() -> TypeofStmt {
             TypeofStmt(cornucopia_async::private::Stmt::new("SELECT * FROM syntax"))
         }
@@ -5153,4 +5924,106 @@ FROM
             }
         }
     }
+    pub mod nested {
+        use super::types;
+        pub mod deep {
+            use super::types;
+            pub mod composite {
+                use cornucopia_async::GenericClient;
+                use futures;
+                use futures::{StreamExt, TryStreamExt};
+                pub struct SuperSuperTypesPublicCopyCompositeQuery<
+                    'a,
+                    C: GenericClient,
+                    T,
+                    const N: usize,
+                > {
+                    client: &'a C,
+                    params: [&'a (dyn postgres_types::ToSql + Sync); N],
+                    stmt: &'a mut cornucopia_async::private::Stmt,
+                    extractor:
+                        fn(&tokio_postgres::Row) -> super::super::types::public::CopyComposite,
+                    mapper: fn(super::super::types::public::CopyComposite) -> T,
+                }
+                impl<'a, C, T: 'a, const N: usize> SuperSuperTypesPublicCopyCompositeQuery<'a, C, T, N>
+                where
+                    C: GenericClient,
+                {
+                    pub fn map<R>(
+                        self,
+                        mapper: fn(super::super::types::public::CopyComposite) -> R,
+                    ) -> SuperSuperTypesPublicCopyCompositeQuery<'a, C, R, N> {
+                        SuperSuperTypesPublicCopyCompositeQuery {
+                            client: self.client,
+                            params: self.params,
+                            stmt: self.stmt,
+                            extractor: self.extractor,
+                            mapper,
+                        }
+                    }
+                    pub async fn one(self) -> Result<T, tokio_postgres::Error> {
+                        let stmt = self.stmt.prepare(self.client).await?;
+                        let row = self.client.query_one(stmt, &self.params).await?;
+                        Ok((self.mapper)((self.extractor)(&row)))
+                    }
+                    pub async fn all(self) -> Result<Vec<T>, tokio_postgres::Error> {
+                        self.iter().await?.try_collect().await
+                    }
+                    pub async fn opt(self) -> Result<Option<T>, tokio_postgres::Error> {
+                        let stmt = self.stmt.prepare(self.client).await?;
+                        Ok(self
+                            .client
+                            .query_opt(stmt, &self.params)
+                            .await?
+                            .map(|row| (self.mapper)((self.extractor)(&row))))
+                    }
+                    pub async fn iter(
+                        self,
+                    ) -> Result<
+                        impl futures::Stream<Item = Result<T, tokio_postgres::Error>> + 'a,
+                        tokio_postgres::Error,
+                    > {
+                        let stmt = self.stmt.prepare(self.client).await?;
+                        let it = self
+                            .client
+                            .query_raw(stmt, cornucopia_async::private::slice_iter(&self.params))
+                            .await?
+                            .map(move |res| res.map(|row| (self.mapper)((self.extractor)(&row))))
+                            .into_stream();
+                        Ok(it)
+                    }
+                }
+                /// <details><summary>SQL</summary>
+                ///
+                /// ```sql
+                /// SELECT * FROM copy;
+                /// ```
+                ///
+                /// </details>
+                pub fn select_nested_copy() -> SelectNestedCopyStmt {
+                    SelectNestedCopyStmt(cornucopia_async::private::Stmt::new("SELECT * FROM copy"))
+                }
+                pub struct SelectNestedCopyStmt(cornucopia_async::private::Stmt);
+                impl SelectNestedCopyStmt {
+                    pub fn bind<'a, C: GenericClient>(
+                        &'a mut self,
+                        client: &'a C,
+                    ) -> SuperSuperTypesPublicCopyCompositeQuery<
+                        'a,
+                        C,
+                        super::super::types::public::CopyComposite,
+                        0,
+                    > {
+                        SuperSuperTypesPublicCopyCompositeQuery {
+                            client,
+                            params: [],
+                            stmt: &mut self.0,
+                            extractor: |row| row.get(0),
+                            mapper: |it| it,
+                        }
+                    }
+                }
+            }
+        }
+    }
 }
@@ -6,7 +6,10 @@
 #[allow(dead_code)]
 pub mod types {
     pub mod public {
-        #[derive(serde::Serialize, Debug, postgres_types :: FromSql, Clone, PartialEq)]
+        #[derive(
+            serde::Serialize, Debug, postgres_types::FromSql, Clone, PartialEq, Eq, Hash, Default,
+        )]
+        #[serde(rename_all = "UPPERCASE")]
         #[postgres(name = "clone_composite")]
         pub struct CloneComposite {
             pub first: i32,
@@ -117,7 +120,7 @@ pub mod types {
                 postgres_types::__to_sql_checked(self, ty, out)
             }
         }
-        #[derive(serde::Serialize, Debug, postgres_types :: FromSql, Copy, Clone, PartialEq)]
+        #[derive(serde::Serialize, Debug, postgres_types::FromSql, Copy, Clone, PartialEq)]
         #[postgres(name = "copy_composite")]
         pub struct CopyComposite {
             pub first: i32,
@@ -186,9 +189,10 @@ pub mod types {
                 postgres_types::__to_sql_checked(self, ty, out)
             }
         }
-        #[derive(serde::Serialize, Debug, postgres_types :: FromSql, Clone, PartialEq)]
+        #[derive(serde::Serialize, Debug, postgres_types::FromSql, Clone, PartialEq)]
         #[postgres(name = "domain_composite")]
         pub struct DomainComposite {
+            /// Some text
             pub txt: String,
             pub json: serde_json::Value,
             pub nb: i32,
@@ -344,10 +348,12 @@ pub mod types {
                 postgres_types::__to_sql_checked(self, ty, out)
             }
         }
-        #[derive(serde::Serialize, Debug, postgres_types :: FromSql, Clone, PartialEq)]
+        /// A composite type
+        #[derive(serde::Serialize, Debug, postgres_types::FromSql, Clone, PartialEq)]
         #[postgres(name = "named_composite")]
         pub struct NamedComposite {
             pub wow: Option<String>,
+            /// How cool it is
             pub such_cool: Option<i32>,
         }
         #[derive(Debug)]
@@ -455,7 +461,7 @@ pub mod types {
                 postgres_types::__to_sql_checked(self, ty, out)
             }
         }
-        #[derive(serde::Serialize, Debug, postgres_types :: FromSql, Clone, PartialEq)]
+        #[derive(serde::Serialize, Debug, postgres_types::FromSql, Clone, PartialEq)]
         #[postgres(name = "nullity_composite")]
         pub struct NullityComposite {
             pub jsons: Option<Vec<Option<serde_json::Value>>>,
@@ -582,7 +588,8 @@ pub mod types {
                 postgres_types::__to_sql_checked(self, ty, out)
             }
         }
-        #[derive(serde::Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+        /// A character from Bikini Bottom
+        #[derive(serde::Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
         #[allow(non_camel_case_types)]
         pub enum SpongebobCharacter {
             Bob,
@@ -664,11 +671,13 @@ pub mod types {
                 }
             }
         }
-        #[derive(serde::Serialize, Debug, postgres_types :: FromSql, Clone, PartialEq)]
+        /// A composite with a custom type
+        #[derive(serde::Serialize, Debug, postgres_types::FromSql, Clone, PartialEq)]
         #[postgres(name = "custom_composite")]
         pub struct CustomComposite {
             pub wow: String,
             pub such_cool: i32,
+            /// Who is nice
             pub nice: super::super::types::public::SpongebobCharacter,
         }
         #[derive(Debug)]
@@ -797,7 +806,7 @@ pub mod types {
                 postgres_types::__to_sql_checked(self, ty, out)
             }
         }
-        #[derive(serde::Serialize, Debug, postgres_types :: FromSql, Clone, PartialEq)]
+        #[derive(serde::Serialize, Debug, postgres_types::FromSql, Clone, PartialEq)]
         #[postgres(name = "nightmare_composite")]
         pub struct NightmareComposite {
             pub custom: Vec<super::super::types::public::CustomComposite>,
@@ -944,7 +953,7 @@ pub mod types {
                 postgres_types::__to_sql_checked(self, ty, out)
             }
         }
-        #[derive(serde::Serialize, Debug, postgres_types :: FromSql, Copy, Clone, PartialEq)]
+        #[derive(serde::Serialize, Debug, postgres_types::FromSql, Copy, Clone, PartialEq)]
         #[postgres(name = "syntax_composite")]
         pub struct SyntaxComposite {
             pub r#async: i32,
@@ -1094,6 +1103,7 @@ pub mod types {
 #[allow(unused_imports)]
 #[allow(dead_code)]
 pub mod queries {
+    use super::types;
     pub mod copy {
         use postgres::{fallible_iterator::FallibleIterator, GenericClient};
         pub struct SuperSuperTypesPublicCloneCompositeQuery<'a, C: GenericClient, T, const N: usize> {
@@ -1198,6 +1208,13 @@ pub mod queries {
                 Ok(it)
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// INSERT INTO clone (composite) VALUES (:composite);
+        /// ```
+        ///
+        /// </details>
         pub fn insert_clone() -> InsertCloneStmt {
             InsertCloneStmt(cornucopia_sync::private::Stmt::new(
                 "INSERT INTO clone (composite) VALUES ($1)",
@@ -1214,6 +1231,13 @@ pub mod queries {
                 client.execute(stmt, &[composite])
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// SELECT * FROM clone;
+        /// ```
+        ///
+        /// </details>
         pub fn select_clone() -> SelectCloneStmt {
             SelectCloneStmt(cornucopia_sync::private::Stmt::new("SELECT * FROM clone"))
         }
@@ -1237,6 +1261,13 @@ pub mod queries {
                 }
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// INSERT INTO copy (composite) VALUES (:composite);
+        /// ```
+        ///
+        /// </details>
         pub fn insert_copy() -> InsertCopyStmt {
             InsertCopyStmt(cornucopia_sync::private::Stmt::new(
                 "INSERT INTO copy (composite) VALUES ($1)",
@@ -1253,6 +1284,13 @@ pub mod queries {
                 client.execute(stmt, &[composite])
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// SELECT * FROM copy;
+        /// ```
+        ///
+        /// </details>
         pub fn select_copy() -> SelectCopyStmt {
             SelectCopyStmt(cornucopia_sync::private::Stmt::new("SELECT * FROM copy"))
         }
@@ -1469,6 +1507,13 @@ pub mod queries {
                 Ok(it)
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// SELECT txt, json, nb, arr FROM nightmare_domain;
+        /// ```
+        ///
+        /// </details>
         pub fn select_nightmare_domain() -> SelectNightmareDomainStmt {
             SelectNightmareDomainStmt(cornucopia_sync::private::Stmt::new(
                 "SELECT txt, json, nb, arr FROM nightmare_domain",
@@ -1494,6 +1539,13 @@ pub mod queries {
                 }
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// INSERT INTO nightmare_domain (txt, json, nb, arr, composite) VALUES (:txt, :json, :nb, :arr, :composite);
+        /// ```
+        ///
+        /// </details>
         pub fn insert_nightmare_domain() -> InsertNightmareDomainStmt {
             InsertNightmareDomainStmt(cornucopia_sync :: private :: Stmt :: new("INSERT INTO nightmare_domain (txt, json, nb, arr, composite) VALUES ($1, $2, $3, $4, $5)"))
         }
@@ -1560,6 +1612,13 @@ pub mod queries {
                 )
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// SELECT * FROM nightmare_domain;
+        /// ```
+        ///
+        /// </details>
         pub fn select_nightmare_domain_null() -> SelectNightmareDomainNullStmt {
             SelectNightmareDomainNullStmt(cornucopia_sync::private::Stmt::new(
                 "SELECT * FROM nightmare_domain",
@@ -1650,10 +1709,12 @@ pub mod queries {
                 Ok(it)
             }
         }
+        /// A named row
         #[derive(serde::Serialize, Debug, Clone, PartialEq)]
         pub struct Named {
             pub id: i32,
             pub name: String,
+            /// The price, if any
             pub price: Option<f64>,
             pub show: bool,
         }
@@ -1779,6 +1840,13 @@ pub mod queries {
                 Ok(it)
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// INSERT INTO named (name, price, show) VALUES (:name, :price, true) RETURNING id ;
+        /// ```
+        ///
+        /// </details>
         pub fn new_named_visible() -> NewNamedVisibleStmt {
             NewNamedVisibleStmt(cornucopia_sync::private::Stmt::new(
                 "INSERT INTO named (name, price, show) VALUES ($1, $2, true) RETURNING id ",
@@ -1813,6 +1881,13 @@ pub mod queries {
                 self.bind(client, &params.name, &params.price)
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// INSERT INTO named (price, name, show) VALUES (:price, :name, false) RETURNING id;
+        /// ```
+        ///
+        /// </details>
         pub fn new_named_hidden() -> NewNamedHiddenStmt {
             NewNamedHiddenStmt(cornucopia_sync::private::Stmt::new(
                 "INSERT INTO named (price, name, show) VALUES ($1, $2, false) RETURNING id",
@@ -1847,6 +1922,13 @@ pub mod queries {
                 self.bind(client, &params.price, &params.name)
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// SELECT * FROM named;
+        /// ```
+        ///
+        /// </details>
         pub fn named() -> NamedStmt {
             NamedStmt(cornucopia_sync::private::Stmt::new("SELECT * FROM named"))
         }
@@ -1870,11 +1952,25 @@ pub mod queries {
                 }
             }
         }
+        /// Selects a named row by id.
+        ///
+        /// Uses the declared `Named` row.
+        ///
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// SELECT * FROM named WHERE id = :id;
+        /// ```
+        ///
+        /// </details>
         pub fn named_by_id() -> NamedByIdStmt {
             NamedByIdStmt(cornucopia_sync::private::Stmt::new(
                 "SELECT * FROM named WHERE id = $1",
             ))
         }
+        /// Selects a named row by id.
+        ///
+        /// Uses the declared `Named` row.
         pub struct NamedByIdStmt(cornucopia_sync::private::Stmt);
         impl NamedByIdStmt {
             pub fn bind<'a, C: GenericClient>(
@@ -1896,11 +1992,21 @@ pub mod queries {
                 }
             }
         }
+        /// Inserts a named composite
+        ///
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// INSERT INTO named_complex (named) VALUES (:named);
+        /// ```
+        ///
+        /// </details>
         pub fn new_named_complex() -> NewNamedComplexStmt {
             NewNamedComplexStmt(cornucopia_sync::private::Stmt::new(
                 "INSERT INTO named_complex (named) VALUES ($1)",
             ))
         }
+        /// Inserts a named composite
         pub struct NewNamedComplexStmt(cornucopia_sync::private::Stmt);
         impl NewNamedComplexStmt {
             pub fn bind<'a, C: GenericClient>(
@@ -1924,6 +2030,13 @@ pub mod queries {
                 self.bind(client, &params.named)
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// SELECT * FROM named_complex;
+        /// ```
+        ///
+        /// </details>
         pub fn named_complex() -> NamedComplexStmt {
             NamedComplexStmt(cornucopia_sync::private::Stmt::new(
                 "SELECT * FROM named_complex",
@@ -2037,6 +2150,13 @@ pub mod queries {
                 Ok(it)
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// INSERT INTO nullity(texts, name, composite) VALUES (:texts, :name, :composite);
+        /// ```
+        ///
+        /// </details>
         pub fn new_nullity() -> NewNullityStmt {
             NewNullityStmt(cornucopia_sync::private::Stmt::new(
                 "INSERT INTO nullity(texts, name, composite) VALUES ($1, $2, $3)",
@@ -2083,6 +2203,13 @@ pub mod queries {
                 self.bind(client, &params.texts, &params.name, &params.composite)
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// SELECT * FROM nullity;
+        /// ```
+        ///
+        /// </details>
         pub fn nullity() -> NullityStmt {
             NullityStmt(cornucopia_sync::private::Stmt::new("SELECT * FROM nullity"))
         }
@@ -2251,6 +2378,13 @@ pub mod queries {
                 Ok(it)
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// INSERT INTO book (author, name) VALUES (:author, :name);
+        /// ```
+        ///
+        /// </details>
         pub fn insert_book() -> InsertBookStmt {
             InsertBookStmt(cornucopia_sync::private::Stmt::new(
                 "INSERT INTO book (author, name) VALUES ($1, $2)",
@@ -2290,6 +2424,13 @@ pub mod queries {
                 self.bind(client, &params.author, &params.name)
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// SELECT * FROM book;
+        /// ```
+        ///
+        /// </details>
         pub fn select_book() -> SelectBookStmt {
             SelectBookStmt(cornucopia_sync::private::Stmt::new("SELECT * FROM book"))
         }
@@ -2311,6 +2452,13 @@ pub mod queries {
                 }
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// SELECT * FROM book WHERE name = ANY (:title);
+        /// ```
+        ///
+        /// </details>
         pub fn find_books() -> FindBooksStmt {
             FindBooksStmt(cornucopia_sync::private::Stmt::new(
                 "SELECT * FROM book WHERE name = ANY ($1)",
@@ -2340,6 +2488,13 @@ pub mod queries {
                 }
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// UPDATE book SET name = :name WHERE length(name) > 42 AND length(:name) < 42;
+        /// ```
+        ///
+        /// </details>
         pub fn params_use_twice() -> ParamsUseTwiceStmt {
             ParamsUseTwiceStmt(cornucopia_sync::private::Stmt::new(
                 "UPDATE book SET name = $1 WHERE length(name) > 42 AND length($1) < 42",
@@ -2356,6 +2511,13 @@ pub mod queries {
                 client.execute(stmt, &[name])
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// UPDATE imaginary SET c=:c, a=:a, z=:a, r=:c;
+        /// ```
+        ///
+        /// </details>
         pub fn params_order() -> ParamsOrderStmt {
             ParamsOrderStmt(cornucopia_sync::private::Stmt::new(
                 "UPDATE imaginary SET c=$1, a=$2, z=$2, r=$1",
@@ -3340,6 +3502,16 @@ pub mod queries {
                 Ok(it)
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// SELECT
+        ///     *
+        /// FROM
+        ///     Everything;
+        /// ```
+        ///
+        /// </details>
         pub fn select_everything() -> SelectEverythingStmt {
             SelectEverythingStmt(cornucopia_sync::private::Stmt::new(
                 "SELECT
@@ -3398,6 +3570,16 @@ FROM
                 }
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// SELECT
+        ///     *
+        /// FROM
+        ///     Everything;
+        /// ```
+        ///
+        /// </details>
         pub fn select_everything_null() -> SelectEverythingNullStmt {
             SelectEverythingNullStmt(cornucopia_sync::private::Stmt::new(
                 "SELECT
@@ -3456,6 +3638,14 @@ FROM
                 }
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// INSERT INTO Everything (bool_, boolean_, char_, smallint_, int2_, smallserial_, serial2_, int_, int4_, serial_, serial4_, bingint_, int8_, bigserial_, serial8_, float4_, real_, float8_, double_precision_, text_, varchar_, bytea_, timestamp_, timestamp_without_time_zone_, timestamptz_, timestamp_with_time_zone_, date_, time_, json_, jsonb_, uuid_, inet_, macaddr_, numeric_)
+        ///     VALUES (:bool_, :boolean_, :char_, :smallint_, :int2_, :smallserial_, :serial2_, :int_, :int4_, :serial_, :serial4_, :bingint_, :int8_, :bigserial_, :serial8_, :float4_, :real_, :float8_, :double_precision_, :text_, :varchar_, :bytea_, :timestamp_, :timestamp_without_time_zone_, :timestamptz_, :timestamp_with_time_zone_, :date_, :time_, :json_, :jsonb_, :uuid_, :inet_, :macaddr_, :numeric_);
+        /// ```
+        ///
+        /// </details>
         pub fn insert_everything() -> InsertEverythingStmt {
             InsertEverythingStmt(cornucopia_sync :: private :: Stmt :: new("INSERT INTO Everything (bool_, boolean_, char_, smallint_, int2_, smallserial_, serial2_, int_, int4_, serial_, serial4_, bingint_, int8_, bigserial_, serial8_, float4_, real_, float8_, double_precision_, text_, varchar_, bytea_, timestamp_, timestamp_without_time_zone_, timestamptz_, timestamp_with_time_zone_, date_, time_, json_, jsonb_, uuid_, inet_, macaddr_, numeric_)
     VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22, $23, $24, $25, $26, $27, $28, $29, $30, $31, $32, $33, $34)"))
@@ -3610,6 +3800,16 @@ FROM
                 )
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// SELECT
+        ///     *
+        /// FROM
+        ///     EverythingArray;
+        /// ```
+        ///
+        /// </details>
         pub fn select_everything_array() -> SelectEverythingArrayStmt {
             SelectEverythingArrayStmt(cornucopia_sync::private::Stmt::new(
                 "SELECT
@@ -3662,6 +3862,16 @@ FROM
                 }
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// SELECT
+        ///     *
+        /// FROM
+        ///     EverythingArray;
+        /// ```
+        ///
+        /// </details>
         pub fn select_everything_array_null() -> SelectEverythingArrayNullStmt {
             SelectEverythingArrayNullStmt(cornucopia_sync::private::Stmt::new(
                 "SELECT
@@ -3714,6 +3924,14 @@ FROM
                 }
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// INSERT INTO EverythingArray (bool_, boolean_, char_, smallint_, int2_, int_, int4_, bingint_, int8_, float4_, real_, float8_, double_precision_, text_, varchar_, bytea_, timestamp_, timestamp_without_time_zone_, timestamptz_, timestamp_with_time_zone_, date_, time_, json_, jsonb_, uuid_, inet_, macaddr_, numeric_)
+        ///     VALUES (:bool_, :boolean_, :char_, :smallint_, :int2_, :int_, :int4_, :bingint_, :int8_, :float4_, :real_, :float8_, :double_precision_, :text_, :varchar_, :bytea_, :timestamp_, :timestamp_without_time_zone_, :timestamptz_, :timestamp_with_time_zone_, :date_, :time_, :json_, :jsonb_, :uuid_, :inet_, :macaddr_, :numeric_);
+        /// ```
+        ///
+        /// </details>
         pub fn insert_everything_array() -> InsertEverythingArrayStmt {
             InsertEverythingArrayStmt(cornucopia_sync :: private :: Stmt :: new("INSERT INTO EverythingArray (bool_, boolean_, char_, smallint_, int2_, int_, int4_, bingint_, int8_, float4_, real_, float8_, double_precision_, text_, varchar_, bytea_, timestamp_, timestamp_without_time_zone_, timestamptz_, timestamp_with_time_zone_, date_, time_, json_, jsonb_, uuid_, inet_, macaddr_, numeric_)
     VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22, $23, $24, $25, $26, $27, $28)"))
@@ -3974,6 +4192,16 @@ FROM
                 )
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// SELECT
+        ///     *
+        /// FROM
+        ///     nightmare;
+        /// ```
+        ///
+        /// </details>
         pub fn select_nightmare() -> SelectNightmareStmt {
             SelectNightmareStmt(cornucopia_sync::private::Stmt::new(
                 "SELECT
@@ -4002,6 +4230,14 @@ FROM
                 }
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// INSERT INTO nightmare (composite)
+        ///     VALUES (:composite);
+        /// ```
+        ///
+        /// </details>
         pub fn insert_nightmare() -> InsertNightmareStmt {
             InsertNightmareStmt(cornucopia_sync::private::Stmt::new(
                 "INSERT INTO nightmare (composite)
@@ -4042,6 +4278,20 @@ FROM
             pub name: T1,
             pub price: f64,
         }
+        /// Inserts a named row, from code like:
+        ///
+        /// ```text
+        /// let id = implicit_multiline().bind(client, &name, &price).one()?;
+        /// ```
+        ///
+        /// ```text
+        /// not rust either
+        /// ```
+        #[derive(Debug)]
+        pub struct ImplicitMultilineParams<T1: cornucopia_sync::StringSql> {
+            pub name: Option<T1>,
+            pub price: Option<f64>,
+        }
         #[derive(Clone, Copy, Debug)]
         pub struct TrickySqlParams {
             pub r#async: super::super::types::public::SyntaxComposite,
@@ -4092,6 +4342,26 @@ FROM
             pub r#async: super::super::types::public::SyntaxComposite,
             pub r#enum: super::super::types::public::SyntaxEnum,
         }
+        #[derive(Clone, Copy, Debug)]
+        pub struct TrickySql11Params {
+            pub r#async: super::super::types::public::SyntaxComposite,
+            pub r#enum: super::super::types::public::SyntaxEnum,
+        }
+        #[derive(Clone, Copy, Debug)]
+        pub struct TrickySql12Params {
+            pub r#async: super::super::types::public::SyntaxComposite,
+            pub r#enum: super::super::types::public::SyntaxEnum,
+        }
+        #[derive(Clone, Copy, Debug)]
+        pub struct TrickySql13Params {
+            pub r#async: super::super::types::public::SyntaxComposite,
+            pub r#enum: super::super::types::public::SyntaxEnum,
+        }
+        #[derive(Clone, Copy, Debug)]
+        pub struct TrickySql14Params {
+            pub r#async: super::super::types::public::SyntaxComposite,
+            pub r#enum: super::super::types::public::SyntaxEnum,
+        }
         pub struct SuperSuperTypesPublicCloneCompositeQuery<'a, C: GenericClient, T, const N: usize> {
             client: &'a mut C,
             params: [&'a (dyn postgres_types::ToSql + Sync); N],
@@ -4191,7 +4461,8 @@ FROM
                 Ok(it)
             }
         }
-        #[derive(serde::Serialize, Debug, Clone, PartialEq, Copy)]
+        #[derive(serde::Serialize, Debug, Clone, PartialEq, Copy, Eq, Hash)]
+        #[serde(rename_all = "PascalCase")]
         pub struct Row {
             pub id: i32,
         }
@@ -4369,6 +4640,13 @@ FROM
                 Ok(it)
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// SELECT * FROM clone;
+        /// ```
+        ///
+        /// </details>
         pub fn select_compact() -> SelectCompactStmt {
             SelectCompactStmt(cornucopia_sync::private::Stmt::new("SELECT * FROM clone"))
         }
@@ -4392,6 +4670,13 @@ FROM
                 }
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// SELECT * FROM clone ;
+        /// ```
+        ///
+        /// </details>
         pub fn select_spaced() -> SelectSpacedStmt {
             SelectSpacedStmt(cornucopia_sync::private::Stmt::new(
                 "      SELECT * FROM clone ",
@@ -4417,6 +4702,13 @@ FROM
                 }
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// INSERT INTO named (name, price, show) VALUES (:name, :price, false) RETURNING id;
+        /// ```
+        ///
+        /// </details>
         pub fn implicit_compact() -> ImplicitCompactStmt {
             ImplicitCompactStmt(cornucopia_sync::private::Stmt::new(
                 "INSERT INTO named (name, price, show) VALUES ($1, $2, false) RETURNING id",
@@ -4455,6 +4747,13 @@ FROM
                 self.bind(client, &params.name, &params.price)
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// INSERT INTO named (name, price, show) VALUES (:name, :price, false) RETURNING id;
+        /// ```
+        ///
+        /// </details>
         pub fn implicit_spaced() -> ImplicitSpacedStmt {
             ImplicitSpacedStmt(cornucopia_sync::private::Stmt::new(
                 "INSERT INTO named (name, price, show) VALUES ($1, $2, false) RETURNING id",
@@ -4493,6 +4792,13 @@ FROM
                 self.bind(client, &params.name, &params.price)
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// INSERT INTO named (name, price, show) VALUES (:name, :price, false) RETURNING id;
+        /// ```
+        ///
+        /// </details>
         pub fn named_compact() -> NamedCompactStmt {
             NamedCompactStmt(cornucopia_sync::private::Stmt::new(
                 "INSERT INTO named (name, price, show) VALUES ($1, $2, false) RETURNING id",
@@ -4527,6 +4833,13 @@ FROM
                 self.bind(client, &params.name, &params.price)
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// INSERT INTO named (name, price, show) VALUES (:name, :price, false) RETURNING id;
+        /// ```
+        ///
+        /// </details>
         pub fn named_spaced() -> NamedSpacedStmt {
             NamedSpacedStmt(cornucopia_sync::private::Stmt::new(
                 "INSERT INTO named (name, price, show) VALUES ($1, $2, false) RETURNING id",
@@ -4561,8 +4874,152 @@ FROM
                 self.bind(client, &params.name, &params.price)
             }
         }
+        /// Inserts a named row, from code like:
+        ///
+        /// ```text
+        /// let id = implicit_multiline().bind(client, &name, &price).one()?;
+        /// ```
+        ///
+        /// ```text
+        /// not rust either
+        /// ```
+        ///
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// INSERT INTO named (name, price, show) VALUES (:name, :price, false) RETURNING id;
+        /// ```
+        ///
+        /// </details>
+        #[deprecated = "use `implicit_compact` instead"]
+        pub fn implicit_multiline() -> ImplicitMultilineStmt {
+            ImplicitMultilineStmt(cornucopia_sync::private::Stmt::new(
+                "INSERT INTO named (name, price, show) VALUES ($1, $2, false) RETURNING id",
+            ))
+        }
+        /// Inserts a named row, from code like:
+        ///
+        /// ```text
+        /// let id = implicit_multiline().bind(client, &name, &price).one()?;
+        /// ```
+        ///
+        /// ```text
+        /// not rust either
+        /// ```
+        pub struct ImplicitMultilineStmt(cornucopia_sync::private::Stmt);
+        impl ImplicitMultilineStmt {
+            pub fn bind<'a, C: GenericClient, T1: cornucopia_sync::StringSql>(
+                &'a mut self,
+                client: &'a mut C,
+                name: &'a Option<T1>,
+                price: &'a Option<f64>,
+            ) -> Optioni32Query<'a, C, Option<i32>, 2> {
+                Optioni32Query {
+                    client,
+                    params: [name, price],
+                    stmt: &mut self.0,
+                    extractor: |row| row.get(0),
+                    mapper: |it| it,
+                }
+            }
+        }
+        impl<'a, C: GenericClient, T1: cornucopia_sync::StringSql>
+            cornucopia_sync::Params<
+                'a,
+                ImplicitMultilineParams<T1>,
+                Optioni32Query<'a, C, Option<i32>, 2>,
+                C,
+            > for ImplicitMultilineStmt
+        {
+            fn params(
+                &'a mut self,
+                client: &'a mut C,
+                params: &'a ImplicitMultilineParams<T1>,
+            ) -> Optioni32Query<'a, C, Option<i32>, 2> {
+                self.bind(client, &params.name, &params.price)
+            }
+        }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// SELECT * FROM clone;
+        /// ```
+        ///
+        /// </details>
+        #[deprecated]
+        pub fn attribute_compact() -> AttributeCompactStmt {
+            AttributeCompactStmt(cornucopia_sync::private::Stmt::new("SELECT * FROM clone"))
+        }
+        pub struct AttributeCompactStmt(cornucopia_sync::private::Stmt);
+        impl AttributeCompactStmt {
+            pub fn bind<'a, C: GenericClient>(
+                &'a mut self,
+                client: &'a mut C,
+            ) -> SuperSuperTypesPublicCloneCompositeQuery<
+                'a,
+                C,
+                super::super::types::public::CloneComposite,
+                0,
+            > {
+                SuperSuperTypesPublicCloneCompositeQuery {
+                    client,
+                    params: [],
+                    stmt: &mut self.0,
+                    extractor: |row| row.get(0),
+                    mapper: |it| it.into(),
+                }
+            }
+        }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// -- : an ordinary comment, not a continuation of the annotation
+        /// -- #[nor is this]
+        /// SELECT * FROM clone;
+        /// ```
+        ///
+        /// </details>
+        #[deprecated]
+        pub fn attribute_passthrough() -> AttributePassthroughStmt {
+            AttributePassthroughStmt(cornucopia_sync::private::Stmt::new(
+                "-- : an ordinary comment, not a continuation of the annotation
+-- #[nor is this]
+SELECT * FROM clone",
+            ))
+        }
+        pub struct AttributePassthroughStmt(cornucopia_sync::private::Stmt);
+        impl AttributePassthroughStmt {
+            pub fn bind<'a, C: GenericClient>(
+                &'a mut self,
+                client: &'a mut C,
+            ) -> SuperSuperTypesPublicCloneCompositeQuery<
+                'a,
+                C,
+                super::super::types::public::CloneComposite,
+                0,
+            > {
+                SuperSuperTypesPublicCloneCompositeQuery {
+                    client,
+                    params: [],
+                    stmt: &mut self.0,
+                    extractor: |row| row.get(0),
+                    mapper: |it| it.into(),
+                }
+            }
+        }
+        impl AttributePassthroughStmt {
+            pub const ATTRIBUTES: &'static [(&'static str, Option<&'static str>)] =
+                &[("timeout", Some("5s")), ("cardinality", Some("one"))];
+        }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// INSERT INTO syntax ("trick:y", async, enum) VALUES ('this is not a bind_param\', :async, :enum);
+        /// ```
+        ///
+        /// </details>
         pub fn tricky_sql() -> TrickySqlStmt {
-            TrickySqlStmt(cornucopia_sync :: private :: Stmt :: new("INSERT INTO syntax (\"trick:y\", async, enum) VALUES ('this is not a bind_param\', $1, $2)"))
+            TrickySqlStmt(cornucopia_sync :: private :: Stmt :: new("INSERT INTO syntax (\"trick:y\", async, enum) VALUES ('this is not a bind_param\\', $1, $2)"))
         }
         pub struct TrickySqlStmt(cornucopia_sync::private::Stmt);
         impl TrickySqlStmt {
@@ -4588,6 +5045,13 @@ FROM
                 self.bind(client, &params.r#async, &params.r#enum)
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// INSERT INTO syntax ("trick:y", async, enum) VALUES ('this is not a :bind_param', :async, :enum);
+        /// ```
+        ///
+        /// </details>
         pub fn tricky_sql1() -> TrickySql1Stmt {
             TrickySql1Stmt(cornucopia_sync :: private :: Stmt :: new("INSERT INTO syntax (\"trick:y\", async, enum) VALUES ('this is not a :bind_param', $1, $2)"))
         }
@@ -4615,6 +5079,13 @@ FROM
                 self.bind(client, &params.r#async, &params.r#enum)
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// INSERT INTO syntax ("trick:y", async, enum) VALUES ('this is not a '':bind_param''', :async, :enum);
+        /// ```
+        ///
+        /// </details>
         pub fn tricky_sql2() -> TrickySql2Stmt {
             TrickySql2Stmt(cornucopia_sync :: private :: Stmt :: new("INSERT INTO syntax (\"trick:y\", async, enum) VALUES ('this is not a '':bind_param''', $1, $2)"))
         }
@@ -4642,6 +5113,13 @@ FROM
                 self.bind(client, &params.r#async, &params.r#enum)
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// INSERT INTO syntax ("trick:y", async, enum)  VALUES ($$this is not a :bind_param$$, :async, :enum);
+        /// ```
+        ///
+        /// </details>
         pub fn tricky_sql3() -> TrickySql3Stmt {
             TrickySql3Stmt(cornucopia_sync :: private :: Stmt :: new("INSERT INTO syntax (\"trick:y\", async, enum)  VALUES ($$this is not a :bind_param$$, $1, $2)"))
         }
@@ -4669,6 +5147,13 @@ FROM
                 self.bind(client, &params.r#async, &params.r#enum)
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// INSERT INTO syntax ("trick:y", async, enum) VALUES ($tag$this is not a :bind_param$tag$, :async, :enum);
+        /// ```
+        ///
+        /// </details>
         pub fn tricky_sql4() -> TrickySql4Stmt {
             TrickySql4Stmt(cornucopia_sync :: private :: Stmt :: new("INSERT INTO syntax (\"trick:y\", async, enum) VALUES ($tag$this is not a :bind_param$tag$, $1, $2)"))
         }
@@ -4696,6 +5181,13 @@ FROM
                 self.bind(client, &params.r#async, &params.r#enum)
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// INSERT INTO syntax ("trick:y", async, enum) VALUES (e'this is not a '':bind_param''', :async, :enum);
+        /// ```
+        ///
+        /// </details>
         pub fn tricky_sql6() -> TrickySql6Stmt {
             TrickySql6Stmt(cornucopia_sync :: private :: Stmt :: new("INSERT INTO syntax (\"trick:y\", async, enum) VALUES (e'this is not a '':bind_param''', $1, $2)"))
         }
@@ -4723,8 +5215,15 @@ FROM
                 self.bind(client, &params.r#async, &params.r#enum)
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// INSERT INTO syntax ("trick:y", async, enum) VALUES (E'this is not a \':bind_param\'', :async, :enum);
+        /// ```
+        ///
+        /// </details>
         pub fn tricky_sql7() -> TrickySql7Stmt {
-            TrickySql7Stmt(cornucopia_sync :: private :: Stmt :: new("INSERT INTO syntax (\"trick:y\", async, enum) VALUES (E'this is not a \':bind_param\'', $1, $2)"))
+            TrickySql7Stmt(cornucopia_sync :: private :: Stmt :: new("INSERT INTO syntax (\"trick:y\", async, enum) VALUES (E'this is not a \\':bind_param\\'', $1, $2)"))
         }
         pub struct TrickySql7Stmt(cornucopia_sync::private::Stmt);
         impl TrickySql7Stmt {
@@ -4750,8 +5249,15 @@ FROM
                 self.bind(client, &params.r#async, &params.r#enum)
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// INSERT INTO syntax ("trick:y", async, enum) VALUES (e'this is ''not'' a \':bind_param\'', :async, :enum);
+        /// ```
+        ///
+        /// </details>
         pub fn tricky_sql8() -> TrickySql8Stmt {
-            TrickySql8Stmt(cornucopia_sync :: private :: Stmt :: new("INSERT INTO syntax (\"trick:y\", async, enum) VALUES (e'this is ''not'' a \':bind_param\'', $1, $2)"))
+            TrickySql8Stmt(cornucopia_sync :: private :: Stmt :: new("INSERT INTO syntax (\"trick:y\", async, enum) VALUES (e'this is ''not'' a \\':bind_param\\'', $1, $2)"))
         }
         pub struct TrickySql8Stmt(cornucopia_sync::private::Stmt);
         impl TrickySql8Stmt {
@@ -4777,8 +5283,15 @@ FROM
                 self.bind(client, &params.r#async, &params.r#enum)
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// INSERT INTO syntax ("trick:y", async, enum) VALUES (E'this is \'not\' a \':bind_param\'', :async, :enum);
+        /// ```
+        ///
+        /// </details>
         pub fn tricky_sql9() -> TrickySql9Stmt {
-            TrickySql9Stmt(cornucopia_sync :: private :: Stmt :: new("INSERT INTO syntax (\"trick:y\", async, enum) VALUES (E'this is \'not\' a \':bind_param\'', $1, $2)"))
+            TrickySql9Stmt(cornucopia_sync :: private :: Stmt :: new("INSERT INTO syntax (\"trick:y\", async, enum) VALUES (E'this is \\'not\\' a \\':bind_param\\'', $1, $2)"))
         }
         pub struct TrickySql9Stmt(cornucopia_sync::private::Stmt);
         impl TrickySql9Stmt {
@@ -4804,6 +5317,13 @@ FROM
                 self.bind(client, &params.r#async, &params.r#enum)
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// INSERT INTO syntax ("trick:y", async, enum) VALUES ('this is just a cast'::text, :async, :enum);
+        /// ```
+        ///
+        /// </details>
         pub fn tricky_sql10() -> TrickySql10Stmt {
             TrickySql10Stmt(cornucopia_sync :: private :: Stmt :: new("INSERT INTO syntax (\"trick:y\", async, enum) VALUES ('this is just a cast'::text, $1, $2)"))
         }
@@ -4831,6 +5351,189 @@ FROM
                 self.bind(client, &params.r#async, &params.r#enum)
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// INSERT INTO syntax ("trick:y", async, enum) VALUES ('this is not a ;', :async, :enum);
+        /// ```
+        ///
+        /// </details>
+        pub fn tricky_sql11() -> TrickySql11Stmt {
+            TrickySql11Stmt(cornucopia_sync::private::Stmt::new(
+                "INSERT INTO syntax (\"trick:y\", async, enum) VALUES ('this is not a ;', $1, $2)",
+            ))
+        }
+        pub struct TrickySql11Stmt(cornucopia_sync::private::Stmt);
+        impl TrickySql11Stmt {
+            pub fn bind<'a, C: GenericClient>(
+                &'a mut self,
+                client: &'a mut C,
+                r#async: &'a super::super::types::public::SyntaxComposite,
+                r#enum: &'a super::super::types::public::SyntaxEnum,
+            ) -> Result<u64, postgres::Error> {
+                let stmt = self.0.prepare(client)?;
+                client.execute(stmt, &[r#async, r#enum])
+            }
+        }
+        impl<'a, C: GenericClient>
+            cornucopia_sync::Params<'a, TrickySql11Params, Result<u64, postgres::Error>, C>
+            for TrickySql11Stmt
+        {
+            fn params(
+                &'a mut self,
+                client: &'a mut C,
+                params: &'a TrickySql11Params,
+            ) -> Result<u64, postgres::Error> {
+                self.bind(client, &params.r#async, &params.r#enum)
+            }
+        }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// INSERT INTO syntax ("trick:y", async, enum) VALUES ($$this is not a ; :bind_param$$, :async, :enum);
+        /// ```
+        ///
+        /// </details>
+        pub fn tricky_sql12() -> TrickySql12Stmt {
+            TrickySql12Stmt(cornucopia_sync :: private :: Stmt :: new("INSERT INTO syntax (\"trick:y\", async, enum) VALUES ($$this is not a ; :bind_param$$, $1, $2)"))
+        }
+        pub struct TrickySql12Stmt(cornucopia_sync::private::Stmt);
+        impl TrickySql12Stmt {
+            pub fn bind<'a, C: GenericClient>(
+                &'a mut self,
+                client: &'a mut C,
+                r#async: &'a super::super::types::public::SyntaxComposite,
+                r#enum: &'a super::super::types::public::SyntaxEnum,
+            ) -> Result<u64, postgres::Error> {
+                let stmt = self.0.prepare(client)?;
+                client.execute(stmt, &[r#async, r#enum])
+            }
+        }
+        impl<'a, C: GenericClient>
+            cornucopia_sync::Params<'a, TrickySql12Params, Result<u64, postgres::Error>, C>
+            for TrickySql12Stmt
+        {
+            fn params(
+                &'a mut self,
+                client: &'a mut C,
+                params: &'a TrickySql12Params,
+            ) -> Result<u64, postgres::Error> {
+                self.bind(client, &params.r#async, &params.r#enum)
+            }
+        }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// INSERT INTO syntax ("trick:y", async, enum) VALUES (E'this is not a \';', :async, :enum);
+        /// ```
+        ///
+        /// </details>
+        pub fn tricky_sql13() -> TrickySql13Stmt {
+            TrickySql13Stmt(cornucopia_sync :: private :: Stmt :: new("INSERT INTO syntax (\"trick:y\", async, enum) VALUES (E'this is not a \\';', $1, $2)"))
+        }
+        pub struct TrickySql13Stmt(cornucopia_sync::private::Stmt);
+        impl TrickySql13Stmt {
+            pub fn bind<'a, C: GenericClient>(
+                &'a mut self,
+                client: &'a mut C,
+                r#async: &'a super::super::types::public::SyntaxComposite,
+                r#enum: &'a super::super::types::public::SyntaxEnum,
+            ) -> Result<u64, postgres::Error> {
+                let stmt = self.0.prepare(client)?;
+                client.execute(stmt, &[r#async, r#enum])
+            }
+        }
+        impl<'a, C: GenericClient>
+            cornucopia_sync::Params<'a, TrickySql13Params, Result<u64, postgres::Error>, C>
+            for TrickySql13Stmt
+        {
+            fn params(
+                &'a mut self,
+                client: &'a mut C,
+                params: &'a TrickySql13Params,
+            ) -> Result<u64, postgres::Error> {
+                self.bind(client, &params.r#async, &params.r#enum)
+            }
+        }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// INSERT INTO syntax ("trick:y", async, enum) VALUES (
+        ///     -- this is not a ; :bind_param
+        ///     /* this is not a ; /* nested */ :bind_param */ 'commented', :async, :enum
+        /// );
+        /// ```
+        ///
+        /// </details>
+        pub fn tricky_sql14() -> TrickySql14Stmt {
+            TrickySql14Stmt(cornucopia_sync::private::Stmt::new(
+                "INSERT INTO syntax (\"trick:y\", async, enum) VALUES (
+    -- this is not a ; :bind_param
+    /* this is not a ; /* nested */ :bind_param */ 'commented', $1, $2
+)",
+            ))
+        }
+        pub struct TrickySql14Stmt(cornucopia_sync::private::Stmt);
+        impl TrickySql14Stmt {
+            pub fn bind<'a, C: GenericClient>(
+                &'a mut self,
+                client: &'a mut C,
+                r#async: &'a super::super::types::public::SyntaxComposite,
+                r#enum: &'a super::super::types::public::SyntaxEnum,
+            ) -> Result<u64, postgres::Error> {
+                let stmt = self.0.prepare(client)?;
+                client.execute(stmt, &[r#async, r#enum])
+            }
+        }
+        impl<'a, C: GenericClient>
+            cornucopia_sync::Params<'a, TrickySql14Params, Result<u64, postgres::Error>, C>
+            for TrickySql14Stmt
+        {
+            fn params(
+                &'a mut self,
+                client: &'a mut C,
+                params: &'a TrickySql14Params,
+            ) -> Result<u64, postgres::Error> {
+                self.bind(client, &params.r#async, &params.r#enum)
+            }
+        }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// DO $$
+        /// BEGIN
+        ///     PERFORM 'this is not a ;';
+        /// END
+        /// $$;
+        /// ```
+        ///
+        /// </details>
+        pub fn tricky_sql15() -> TrickySql15Stmt {
+            TrickySql15Stmt(cornucopia_sync::private::Stmt::new(
+                "DO $$
+BEGIN
+    PERFORM 'this is not a ;';
+END
+$$",
+            ))
+        }
+        pub struct TrickySql15Stmt(cornucopia_sync::private::Stmt);
+        impl TrickySql15Stmt {
+            pub fn bind<'a, C: GenericClient>(
+                &'a mut self,
+                client: &'a mut C,
+            ) -> Result<u64, postgres::Error> {
+                let stmt = self.0.prepare(client)?;
+                client.execute(stmt, &[])
+            }
+        }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// SELECT * FROM syntax;
+        /// ```
+        ///
+        /// </details>
         pub fn r#typeof() -> TypeofStmt {
             TypeofStmt(cornucopia_sync::private::Stmt::new("SELECT * FROM syntax"))
         }
@@ -4854,4 +5557,101 @@ FROM
             }
         }
     }
+    pub mod nested {
+        use super::types;
+        pub mod deep {
+            use super::types;
+            pub mod composite {
+                use postgres::{fallible_iterator::FallibleIterator, GenericClient};
+                pub struct SuperSuperTypesPublicCopyCompositeQuery<
+                    'a,
+                    C: GenericClient,
+                    T,
+                    const N: usize,
+                > {
+                    client: &'a mut C,
+                    params: [&'a (dyn postgres_types::ToSql + Sync); N],
+                    stmt: &'a mut cornucopia_sync::private::Stmt,
+                    extractor: fn(&postgres::Row) -> super::super::types::public::CopyComposite,
+                    mapper: fn(super::super::types::public::CopyComposite) -> T,
+                }
+                impl<'a, C, T: 'a, const N: usize> SuperSuperTypesPublicCopyCompositeQuery<'a, C, T, N>
+                where
+                    C: GenericClient,
+                {
+                    pub fn map<R>(
+                        self,
+                        mapper: fn(super::super::types::public::CopyComposite) -> R,
+                    ) -> SuperSuperTypesPublicCopyCompositeQuery<'a, C, R, N> {
+                        SuperSuperTypesPublicCopyCompositeQuery {
+                            client: self.client,
+                            params: self.params,
+                            stmt: self.stmt,
+                            extractor: self.extractor,
+                            mapper,
+                        }
+                    }
+                    pub fn one(self) -> Result<T, postgres::Error> {
+                        let stmt = self.stmt.prepare(self.client)?;
+                        let row = self.client.query_one(stmt, &self.params)?;
+                        Ok((self.mapper)((self.extractor)(&row)))
+                    }
+                    pub fn all(self) -> Result<Vec<T>, postgres::Error> {
+                        self.iter()?.collect()
+                    }
+                    pub fn opt(self) -> Result<Option<T>, postgres::Error> {
+                        let stmt = self.stmt.prepare(self.client)?;
+                        Ok(self
+                            .client
+                            .query_opt(stmt, &self.params)?
+                            .map(|row| (self.mapper)((self.extractor)(&row))))
+                    }
+                    pub fn iter(
+                        self,
+                    ) -> Result<
+                        impl Iterator<Item = Result<T, postgres::Error>> + 'a,
+                        postgres::Error,
+                    > {
+                        let stmt = self.stmt.prepare(self.client)?;
+                        let it = self
+                            .client
+                            .query_raw(stmt, cornucopia_sync::private::slice_iter(&self.params))?
+                            .iterator()
+                            .map(move |res| res.map(|row| (self.mapper)((self.extractor)(&row))));
+                        Ok(it)
+                    }
+                }
+                /// <details><summary>SQL</summary>
+                ///
+                /// ```sql
+                /// SELECT * FROM copy;
+                /// ```
+                ///
+                /// </details>
+                pub fn select_nested_copy() -> SelectNestedCopyStmt {
+                    SelectNestedCopyStmt(cornucopia_sync::private::Stmt::new("SELECT * FROM copy"))
+                }
+                pub struct SelectNestedCopyStmt(cornucopia_sync::private::Stmt);
+                impl SelectNestedCopyStmt {
+                    pub fn bind<'a, C: GenericClient>(
+                        &'a mut self,
+                        client: &'a mut C,
+                    ) -> SuperSuperTypesPublicCopyCompositeQuery<
+                        'a,
+                        C,
+                        super::super::types::public::CopyComposite,
+                        0,
+                    > {
+                        SuperSuperTypesPublicCopyCompositeQuery {
+                            client,
+                            params: [],
+                            stmt: &mut self.0,
+                            extractor: |row| row.get(0),
+                            mapper: |it| it,
+                        }
+                    }
+                }
+            }
+        }
+    }
 }
@@ -8,7 +8,7 @@ use rust_decimal::Decimal;
 use serde_json::Value;
 use std::{
     borrow::Cow,
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     net::{IpAddr, Ipv4Addr},
 };
 use time::{OffsetDateTime, PrimitiveDateTime};
@@ -25,6 +25,7 @@ use crate::cornucopia_sync::{
             named, named_by_id, named_complex, new_named_complex, new_named_hidden,
             new_named_visible, Named, NamedComplexParams, NamedParams,
         },
+        nested::deep::composite::select_nested_copy,
         nullity::{new_nullity, nullity},
         nullity::{Nullity, NullityParams},
         params::insert_book,
@@ -34,13 +35,16 @@ use crate::cornucopia_sync::{
             select_everything_array, select_nightmare, Everything, EverythingArray,
             EverythingArrayParams, EverythingParams,
         },
-        syntax::{r#typeof, tricky_sql10, TrickySql10Params},
+        syntax::{
+            self, r#typeof, tricky_sql10, tricky_sql11, tricky_sql12, tricky_sql13, tricky_sql14,
+            tricky_sql15, TrickySql10Params,
+        },
     },
     types::public::{
-        CloneCompositeBorrowed, CopyComposite, CustomComposite, CustomCompositeBorrowed,
-        DomainComposite, DomainCompositeParams, NamedComposite, NamedCompositeBorrowed,
-        NightmareComposite, NightmareCompositeParams, NullityComposite, NullityCompositeParams,
-        SpongebobCharacter, SyntaxComposite, SyntaxEnum,
+        CloneComposite, CloneCompositeBorrowed, CopyComposite, CustomComposite,
+        CustomCompositeBorrowed, DomainComposite, DomainCompositeParams, NamedComposite,
+        NamedCompositeBorrowed, NightmareComposite, NightmareCompositeParams, NullityComposite,
+        NullityCompositeParams, SpongebobCharacter, SyntaxComposite, SyntaxEnum,
     },
 };
 use ::cornucopia_sync::Params;
@@ -55,6 +59,7 @@ pub fn main() {
         .connect(NoTls)
         .unwrap();
     test_copy(client);
+    test_nested_modules(client);
     test_params(client);
     test_named(client);
     test_nullity(client);
@@ -62,6 +67,9 @@ pub fn main() {
     test_domain(client);
     test_trait_sql(client);
     test_keyword_escaping(client);
+    test_sql_lexing(client);
+    test_annotation_attributes(client);
+    test_derives();
 }
 
 pub fn moving<T>(_item: T) {}
@@ -280,6 +288,19 @@ pub fn test_copy(client: &mut Client) {
     select_copy().bind(client).one().unwrap();
 }
 
+// Test queries in nested modules
+pub fn test_nested_modules(client: &mut Client) {
+    // Row inserted by `test_copy`
+    let copy_row: CopyComposite = select_nested_copy().bind(client).one().unwrap();
+    assert_eq!(
+        CopyComposite {
+            first: 42,
+            second: 4.2,
+        },
+        copy_row
+    );
+}
+
 // Test domain erasing
 pub fn test_domain(client: &mut Client) {
     let json: Value = serde_json::from_str(r#"{"name": "James Bond"}"#).unwrap();
@@ -530,3 +551,74 @@ pub fn test_keyword_escaping(client: &mut Client) {
     tricky_sql10().params(client, &params).unwrap();
     r#typeof().bind(client).all().unwrap();
 }
+
+// Test that `;` only ends a query outside of strings and comments
+pub fn test_sql_lexing(client: &mut Client) {
+    let params = TrickySql10Params {
+        r#async: SyntaxComposite { r#async: 34 },
+        r#enum: SyntaxEnum::r#box,
+    };
+    tricky_sql11()
+        .bind(client, &params.r#async, &params.r#enum)
+        .unwrap();
+    tricky_sql12()
+        .bind(client, &params.r#async, &params.r#enum)
+        .unwrap();
+    tricky_sql13()
+        .bind(client, &params.r#async, &params.r#enum)
+        .unwrap();
+    tricky_sql14()
+        .bind(client, &params.r#async, &params.r#enum)
+        .unwrap();
+    tricky_sql15().bind(client).unwrap();
+}
+
+// Test annotations spanning several lines and query attributes
+#[allow(deprecated)]
+pub fn test_annotation_attributes(client: &mut Client) {
+    let id: Option<i32> = syntax::implicit_multiline()
+        .bind(client, &Some("Multiline"), &Some(7.5))
+        .one()
+        .unwrap();
+    assert!(id.is_some());
+    syntax::attribute_compact().bind(client).all().unwrap();
+    // Attributes unknown to cornucopia are passed through
+    syntax::attribute_passthrough().bind(client).all().unwrap();
+    assert_eq!(
+        syntax::AttributePassthroughStmt::ATTRIBUTES,
+        [("timeout", Some("5s")), ("cardinality", Some("one"))]
+    );
+}
+
+// Test the derives and attributes configured in the codegen fixture
+pub fn test_derives() {
+    let composite = CloneComposite {
+        first: 42,
+        second: "Hello".to_string(),
+    };
+    let set: HashSet<_> = [
+        composite.clone(),
+        composite.clone(),
+        CloneComposite::default(),
+    ]
+    .into();
+    assert_eq!(set.len(), 2);
+    assert_eq!(
+        serde_json::to_value(&composite).unwrap(),
+        serde_json::json!({ "FIRST": 42, "SECOND": "Hello" })
+    );
+    assert!(SpongebobCharacter::Bob < SpongebobCharacter::Squidward);
+    assert_eq!(
+        serde_json::to_value(syntax::Row { id: 7 }).unwrap(),
+        serde_json::json!({ "Id": 7 })
+    );
+    // The async module is generated with a global attribute
+    let composite = crate::cornucopia_async::types::public::NamedComposite {
+        wow: None,
+        such_cool: Some(3),
+    };
+    assert_eq!(
+        serde_json::to_value(composite).unwrap(),
+        serde_json::json!({ "wow": null, "suchCool": 3 })
+    );
+}
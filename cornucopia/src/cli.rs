@@ -1,84 +1,474 @@
 use clap::{Parser, Subcommand};
 
-use crate::{conn, container, error::Error, generate_live, generate_managed, CodegenSettings};
+use crate::{
+    check_generated_code,
+    config::{
+        error::Error as ConfigError, Config, ContainerConfig, DEFAULT_DESTINATION,
+        DEFAULT_QUERIES_PATH,
+    },
+    conn,
+    container::ContainerSettings,
+    dump_schema::{dump_schema, error::Error as DumpSchemaError},
+    error::{DeniedWarningsError, Error},
+    explain::explain,
+    format_queries::format_queries,
+    generate_live_with_metadata, generate_managed_with_metadata, generate_offline,
+    load_schema::{load_schema, SchemaReloader},
+    lsp,
+    message_format::MessageFormat,
+    metadata::{Metadata, StmtSource},
+    watch, with_managed_db, with_managed_server, CodegenSettings, DeriveSettings, GeneratedCode,
+    ManagedBackend, TypeDerives,
+};
 
 /// Command line interface to interact with Cornucopia SQL.
+///
+/// Settings are also read from the closest `cornucopia.toml`, flags taking precedence.
+/// Flags set there can be turned off with their `--no-` variant, like `--no-sync`.
 #[derive(Parser, Debug)]
 #[clap(version)]
 struct Args {
-    /// Use `podman` instead of `docker`
+    /// Configuration file to use instead of the closest `cornucopia.toml`
+    #[clap(long)]
+    config: Option<String>,
+    /// Use the local `initdb` and `pg_ctl` binaries instead of a container
+    #[clap(long, conflicts_with_all = ["podman", "runtime"], overrides_with = "no_local")]
+    local: bool,
+    /// Use a container, even if `local` is set in the configuration file
+    #[clap(long, overrides_with = "local")]
+    no_local: bool,
+    /// Folder containing the queries [default: queries/]
     #[clap(short, long)]
-    podman: bool,
-    /// Folder containing the queries
-    #[clap(short, long, default_value = "queries/")]
-    queries_path: String,
-    /// Destination folder for generated modules
-    #[clap(short, long, default_value = "src/cornucopia.rs")]
-    destination: String,
+    queries_path: Option<String>,
+    /// Destination file for generated modules. Ending with a `/`, like `src/cornucopia/`, it is
+    /// a directory receiving one file per module [default: src/cornucopia.rs]
+    #[clap(short, long)]
+    destination: Option<String>,
+    /// Write the prepared queries metadata to this file, for later offline generation
+    #[clap(long)]
+    metadata: Option<String>,
     #[clap(subcommand)]
-    action: Action,
+    action: Option<Action>,
     /// Generate synchronous rust code. Async otherwise.
-    #[clap(long)]
+    #[clap(long, overrides_with = "no_sync")]
     sync: bool,
+    /// Generate asynchronous rust code, even if `sync` is set in the configuration file
+    #[clap(long, overrides_with = "sync")]
+    no_sync: bool,
     /// Derive serde's `Serialize` trait for generated types.
-    #[clap(long)]
+    #[clap(long, overrides_with = "no_serialize")]
     serialize: bool,
+    /// Don't derive `Serialize`, even if `serialize` is set in the configuration file
+    #[clap(long, overrides_with = "serialize")]
+    no_serialize: bool,
+    /// Derive this trait for every row struct and Postgres type, like `Hash`. Can be repeated
+    #[clap(long = "derive", value_name = "DERIVE", value_delimiter = ',')]
+    derives: Vec<String>,
+    /// Add this attribute to every row struct and Postgres type, like `#[deprecated]`. Can be repeated
+    #[clap(long = "attribute", value_name = "ATTRIBUTE")]
+    attributes: Vec<String>,
+    /// Keep running, and generate again whenever the queries or the schema change
+    #[clap(long)]
+    watch: bool,
+    /// Fail when warnings are reported, after writing the generated code
+    #[clap(long, overrides_with = "no_deny_warnings")]
+    deny_warnings: bool,
+    /// Don't fail on warnings, even if `deny-warnings` is set in the configuration file
+    #[clap(long, overrides_with = "deny_warnings")]
+    no_deny_warnings: bool,
+    /// Format of the printed errors and warnings
+    #[clap(long, value_enum, default_value_t)]
+    message_format: MessageFormat,
+    #[clap(flatten)]
+    container: ContainerArgs,
+}
+
+/// Settings of the container used to generate against schema files.
+/// Unset values fallback to `ContainerSettings::default()`.
+#[derive(clap::Args, Debug)]
+#[clap(next_help_heading = "Container")]
+struct ContainerArgs {
+    /// Use `podman` instead of `docker`
+    #[clap(short, long)]
+    podman: bool,
+    /// Container runtime binary to use, like `nerdctl` [default: docker]
+    #[clap(long, conflicts_with = "podman")]
+    runtime: Option<String>,
+    /// PostgreSQL image of the container [default: postgres]
+    #[clap(long)]
+    image: Option<String>,
+    /// Tag of the PostgreSQL image [default: latest]
+    #[clap(long)]
+    tag: Option<String>,
+    /// Name of the container [default: unique to each run]
+    #[clap(long)]
+    container_name: Option<String>,
+    /// Host port on which the container is exposed [default: assigned by the OS]
+    #[clap(long)]
+    port: Option<u16>,
+    /// PostgreSQL superuser of the container [default: postgres]
+    #[clap(long)]
+    db_user: Option<String>,
+    /// Password of the PostgreSQL superuser [default: postgres]
+    #[clap(long)]
+    db_password: Option<String>,
+    /// Keep the container running to reuse it in the next runs, each run working in a fresh database
+    #[clap(long, overrides_with = "no_keep_alive")]
+    keep_alive: bool,
+    /// Remove the container after the run, even if `keep-alive` is set in the configuration file
+    #[clap(long, overrides_with = "keep_alive")]
+    no_keep_alive: bool,
+}
+
+impl ContainerArgs {
+    /// Whether a container runtime was chosen on the command line.
+    fn has_runtime(&self) -> bool {
+        self.podman || self.runtime.is_some()
+    }
+
+    fn into_settings(self, config: ContainerConfig) -> ContainerSettings {
+        let default = ContainerSettings::default();
+        let runtime = if self.podman {
+            Some(String::from("podman"))
+        } else {
+            self.runtime
+        };
+        ContainerSettings {
+            runtime: runtime.or(config.runtime).unwrap_or(default.runtime),
+            image: self.image.or(config.image).unwrap_or(default.image),
+            tag: self.tag.or(config.tag).unwrap_or(default.tag),
+            name: self.container_name.or(config.name).or(default.name),
+            port: self.port.or(config.port).or(default.port),
+            user: self.db_user.or(config.user).unwrap_or(default.user),
+            password: self
+                .db_password
+                .or(config.password)
+                .unwrap_or(default.password),
+            keep_alive: flag(
+                self.keep_alive,
+                self.no_keep_alive,
+                config.keep_alive.unwrap_or(default.keep_alive),
+            ),
+        }
+    }
 }
 
 #[derive(Debug, Subcommand)]
 enum Action {
+    #[clap(flatten)]
+    Generate(Source),
+    /// Check that the destination file is up to date, without modifying it
+    Check {
+        #[clap(subcommand)]
+        source: Option<Source>,
+    },
+    /// Snapshot the schema of a live database with `pg_dump`, to generate against it with `schema`
+    DumpSchema {
+        /// Postgres url or `key=value` connection string
+        /// [default: `url` from `cornucopia.toml`, or `DATABASE_URL`]
+        url: Option<String>,
+        /// File receiving the snapshot [default: standard output]
+        #[clap(short, long)]
+        output: Option<String>,
+    },
+    /// Format the annotations of the query files. The SQL of the queries is left as written
+    Fmt {
+        /// Check that the query files are formatted, without modifying them
+        #[clap(long)]
+        check: bool,
+    },
+    /// Run a language server for query files on the standard input and output, preparing
+    /// queries against the given source
+    Lsp {
+        #[clap(subcommand)]
+        source: Option<Source>,
+    },
+    /// Print the extended explanation of an error or warning code, like `E0007`
+    Explain {
+        /// Code printed before the error or warning
+        code: String,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum Source {
     /// Generate your modules against your own db
+    ///
+    /// Like with `psql`, parameters missing from the url are read from the `PG*` environment
+    /// variables and the service file, and the password from `~/.pgpass`.
     Live {
-        /// Postgres url to the database
-        url: String,
+        /// Postgres url or `key=value` connection string
+        /// [default: `url` from `cornucopia.toml`, or `DATABASE_URL`]
+        url: Option<String>,
     },
     /// Generate your modules against schema files
     Schema {
-        /// SQL files containing the database schema
+        /// SQL files containing the database schema, or migrations directories whose migrations
+        /// are loaded in version order. Settings changed with `SET` don't carry over from a file
+        /// to the next [default: `schema-files` from `cornucopia.toml`]
         schema_files: Vec<String>,
     },
+    /// Generate your modules without database, using previously written metadata
+    Offline {
+        /// Metadata file written using `--metadata` [default: `metadata` from `cornucopia.toml`]
+        metadata_file: Option<String>,
+    },
 }
 
 // Main entrypoint of the CLI. Parses the args and calls the appropriate routines.
 pub fn run() -> Result<(), Error> {
+    let args = Args::parse();
+    let message_format = args.message_format;
+    match run_args(args) {
+        // Errors are rendered by the caller, except for tools expecting JSON
+        Err(err) if message_format == MessageFormat::Json => {
+            message_format.print(err);
+            std::process::exit(1)
+        }
+        result => result,
+    }
+}
+
+/// Resolves a flag which can also be set in the configuration file: `--<flag>` turns it on and
+/// `--no-<flag>` off, whatever its `configured` value.
+fn flag(enabled: bool, disabled: bool, configured: bool) -> bool {
+    enabled || (configured && !disabled)
+}
+
+fn run_args(args: Args) -> Result<(), Error> {
     let Args {
-        podman,
+        config,
+        local,
+        no_local,
         queries_path,
         destination,
+        metadata,
         action,
         sync,
+        no_sync,
         serialize,
-    } = Args::parse();
+        no_serialize,
+        derives,
+        attributes,
+        watch,
+        deny_warnings,
+        no_deny_warnings,
+        message_format,
+        container,
+    } = args;
+    let config = Config::load(config.as_deref())?;
 
-    match action {
-        Action::Live { url } => {
-            let mut client = conn::from_url(&url)?;
-            generate_live(
-                &mut client,
-                &queries_path,
-                Some(&destination),
-                CodegenSettings {
-                    is_async: !sync,
-                    derive_ser: serialize,
-                },
+    let queries_path = queries_path
+        .or(config.queries_path)
+        .unwrap_or_else(|| String::from(DEFAULT_QUERIES_PATH));
+    let destination = destination
+        .or(config.destination)
+        .unwrap_or_else(|| String::from(DEFAULT_DESTINATION));
+    let metadata = metadata.or(config.metadata);
+    let deny_warnings = flag(
+        deny_warnings,
+        no_deny_warnings,
+        config.deny_warnings.unwrap_or(false),
+    );
+    // Derives and attributes from the command line come after the configured ones
+    let global = TypeDerives {
+        derives: config
+            .derives
+            .unwrap_or_default()
+            .into_iter()
+            .chain(derives)
+            .collect(),
+        attributes: config
+            .attributes
+            .unwrap_or_default()
+            .into_iter()
+            .chain(attributes)
+            .collect(),
+    };
+    let settings = CodegenSettings {
+        is_async: !flag(sync, no_sync, config.sync.unwrap_or(false)),
+        derive_ser: flag(serialize, no_serialize, config.serialize.unwrap_or(false)),
+        derives: DeriveSettings {
+            global,
+            types: config
+                .types
+                .into_iter()
+                .map(|(k, v)| (k, v.into()))
+                .collect(),
+            structs: config
+                .structs
+                .into_iter()
+                .map(|(k, v)| (k, v.into()))
+                .collect(),
+        },
+    };
+    let (source, check, serve) = match action {
+        Some(Action::Generate(source)) => (Some(source), false, false),
+        Some(Action::Check { source }) => (source, true, false),
+        Some(Action::Lsp { source }) => (source, false, true),
+        Some(Action::DumpSchema { url, output }) => {
+            let snapshot = dump_schema(url.or(config.url).as_deref())?;
+            match output {
+                Some(path) => std::fs::write(&path, snapshot)
+                    .map_err(|err| DumpSchemaError::Write { path, err })?,
+                None => print!("{snapshot}"),
+            }
+            return Ok(());
+        }
+        Some(Action::Fmt { check }) => {
+            format_queries(&queries_path, check, &mut message_format.diff_output())?;
+            return Ok(());
+        }
+        Some(Action::Explain { code }) => {
+            print!("{}", explain(&code)?);
+            return Ok(());
+        }
+        None => (None, false, false),
+    };
+    // Without any command, the database comes from the configuration file
+    let source = match source {
+        Some(source) => source,
+        None if config.url.is_some() => Source::Live { url: None },
+        None if config.schema_files.is_some() => Source::Schema {
+            schema_files: Vec::new(),
+        },
+        None => return Err(ConfigError::MissingSource.into()),
+    };
+    // When checking, the destination and metadata files must be left untouched
+    let write_destination = (!check).then_some(destination.as_str());
+    let write_metadata = metadata.as_deref().filter(|_| !check);
+
+    let check_destination = |generated_code: GeneratedCode| {
+        let warnings = generated_code.warnings();
+        for warning in warnings {
+            message_format.print(warning.clone());
+        }
+        if check {
+            check_generated_code(
+                &destination,
+                &generated_code,
+                &mut message_format.diff_output(),
             )?;
         }
-        Action::Schema { schema_files } => {
-            // Run the generate command. If the command is unsuccessful, cleanup Cornucopia's container
-            if let Err(e) = generate_managed(
-                &queries_path,
-                schema_files,
-                Some(&destination),
-                podman,
-                CodegenSettings {
-                    is_async: !sync,
-                    derive_ser: serialize,
-                },
-            ) {
-                container::cleanup(podman).ok();
-                return Err(e);
+        if deny_warnings && !warnings.is_empty() {
+            return Err(DeniedWarningsError {
+                count: warnings.len(),
             }
+            .into());
         }
+        Ok(())
     };
-    Ok(())
+
+    match source {
+        Source::Live { url } => {
+            let mut client = conn::connect(url.or(config.url).as_deref())?;
+            if serve {
+                return Ok(lsp::serve(&mut StmtSource::live(&mut client), settings)?);
+            }
+            run_or_watch(watch, &[&queries_path], message_format, || {
+                check_destination(generate_live_with_metadata(
+                    &mut client,
+                    &queries_path,
+                    write_destination,
+                    write_metadata,
+                    settings.clone(),
+                )?)
+            })
+        }
+        Source::Schema { mut schema_files } => {
+            if schema_files.is_empty() {
+                schema_files = config.schema_files.unwrap_or_default();
+            }
+            if schema_files.is_empty() {
+                return Err(ConfigError::MissingSetting {
+                    setting: "schema-files",
+                    command: "schema",
+                }
+                .into());
+            }
+            // Choosing a runtime on the command line overrides `local` from the configuration
+            let configured = config.local.unwrap_or(false) && !container.has_runtime();
+            let local = flag(local, no_local, configured);
+            let backend = if local {
+                ManagedBackend::Local
+            } else {
+                ManagedBackend::Container(container.into_settings(config.container))
+            };
+            if serve {
+                return with_managed_db(backend, |client| {
+                    load_schema(client, schema_files)?;
+                    Ok(lsp::serve(&mut StmtSource::live(client), settings)?)
+                });
+            }
+            if watch {
+                // The database server is kept between generations, the schema is reloaded
+                // into a fresh database
+                let mut watched = vec![queries_path.as_str()];
+                watched.extend(schema_files.iter().map(String::as_str));
+                with_managed_server(backend, |config| {
+                    let mut reloader = SchemaReloader::new(config)?;
+                    let result = run_or_watch(true, &watched, message_format, || {
+                        let mut client = reloader.reload(schema_files.clone())?;
+                        check_destination(generate_live_with_metadata(
+                            &mut client,
+                            &queries_path,
+                            write_destination,
+                            write_metadata,
+                            settings.clone(),
+                        )?)
+                    });
+                    reloader.cleanup()?;
+                    result
+                })
+            } else {
+                check_destination(generate_managed_with_metadata(
+                    &queries_path,
+                    schema_files,
+                    write_destination,
+                    write_metadata,
+                    backend,
+                    settings,
+                )?)
+            }
+        }
+        Source::Offline { metadata_file } => {
+            let metadata_file = metadata_file
+                .or(metadata)
+                .ok_or(ConfigError::MissingSetting {
+                    setting: "metadata",
+                    command: "offline",
+                })?;
+            if serve {
+                let metadata = Metadata::read(&metadata_file)?;
+                return Ok(lsp::serve(&mut StmtSource::offline(metadata), settings)?);
+            }
+            run_or_watch(
+                watch,
+                &[&queries_path, &metadata_file],
+                message_format,
+                || {
+                    check_destination(generate_offline(
+                        &queries_path,
+                        &metadata_file,
+                        write_destination,
+                        settings.clone(),
+                    )?)
+                },
+            )
+        }
+    }
+}
+
+/// Runs `f` once, or each time a file in `paths` changes if `watch` is set.
+fn run_or_watch(
+    watch: bool,
+    paths: &[&str],
+    message_format: MessageFormat,
+    mut f: impl FnMut() -> Result<(), Error>,
+) -> Result<(), Error> {
+    if watch {
+        Ok(watch::watch(paths, message_format, f)?)
+    } else {
+        f()
+    }
 }
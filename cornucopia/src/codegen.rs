@@ -1,17 +1,23 @@
 use core::str;
-use std::fmt::Write;
+use std::{
+    fmt::{Display, Write},
+    path::{Path, PathBuf},
+};
 
 use code::code;
 use heck::ToUpperCamelCase;
 use indexmap::IndexMap;
 
+use self::error::Error;
 use crate::{
     prepare_queries::{
         Preparation, PreparedContent, PreparedField, PreparedItem, PreparedModule, PreparedQuery,
         PreparedType,
     },
+    read_queries::ModuleInfo,
     utils::{escape_keyword, unescape_keyword},
-    CodegenSettings,
+    warning::Warning,
+    CodegenSettings, DeriveSettings, TypeDerives,
 };
 
 impl PreparedField {
@@ -276,19 +282,150 @@ fn composite_fromsql(
     );
 }
 
-fn gen_params_struct(w: &mut impl Write, params: &PreparedItem, settings: CodegenSettings) {
+/// Writes a line of documentation as a `///` comment.
+fn gen_doc_line(w: &mut impl Write, line: &str) {
+    if line.is_empty() {
+        writeln!(w, "///").unwrap();
+    } else {
+        writeln!(w, "/// {line}").unwrap();
+    }
+}
+
+/// Writes comments as `///` documentation. Their code blocks are rarely Rust, so those
+/// without a language are marked as `text`, to keep rustdoc from running them as doctests.
+fn gen_doc(w: &mut impl Write, doc: &[String]) {
+    let mut in_fence = false;
+    let mut in_indented = false;
+    let mut after_blank = true;
+    // Blank lines of an indented code block, which may end it
+    let mut blanks = 0;
+    for line in doc {
+        let trimmed = line.trim_start();
+        let is_indented = line.starts_with("    ") || line.starts_with('\t');
+        if in_indented {
+            if trimmed.is_empty() {
+                blanks += 1;
+                continue;
+            }
+            if !is_indented {
+                gen_doc_line(w, "```");
+                in_indented = false;
+            }
+            for _ in 0..std::mem::take(&mut blanks) {
+                gen_doc_line(w, "");
+            }
+        }
+        if let Some(info) = trimmed.strip_prefix("```") {
+            if !in_fence && info.trim().is_empty() {
+                gen_doc_line(w, &format!("{line}text"));
+            } else {
+                gen_doc_line(w, line);
+            }
+            in_fence = !in_fence;
+        } else if in_fence {
+            gen_doc_line(w, line);
+        } else if in_indented || (is_indented && after_blank) {
+            // An indented code block, turned into a fenced one
+            if !in_indented {
+                gen_doc_line(w, "```text");
+                in_indented = true;
+            }
+            let line = line.strip_prefix("    ").unwrap_or(line);
+            gen_doc_line(w, line.strip_prefix('\t').unwrap_or(line));
+        } else {
+            gen_doc_line(w, line);
+        }
+        after_blank = trimmed.is_empty();
+    }
+    if in_indented {
+        gen_doc_line(w, "```");
+    }
+}
+
+/// Documents a query with its comments, followed by its SQL in a collapsible block.
+fn gen_query_doc(w: &mut impl Write, doc: &[String], source_sql: &str) {
+    gen_doc(w, doc);
+    if !doc.is_empty() {
+        gen_doc_line(w, "");
+    }
+    ["<details><summary>SQL</summary>", "", "```sql"]
+        .into_iter()
+        .chain(source_sql.lines())
+        .chain(["```", "", "</details>"])
+        .for_each(|line| gen_doc_line(w, line));
+}
+
+/// Renders lines of documentation as `///` comments, for items interpolated in a repetition.
+fn doc_string(doc: &[String]) -> String {
+    let mut s = String::new();
+    gen_doc(&mut s, doc);
+    s
+}
+
+/// Writes the derive attribute of an item, followed by its `configured` attributes.
+/// The configured derives come after the `builtin` ones, which are not repeated.
+fn gen_derives(w: &mut impl Write, builtin: &[&str], configured: &[&TypeDerives]) {
+    let mut all: Vec<&str> = builtin.to_vec();
+    for derive in configured.iter().flat_map(|it| &it.derives) {
+        let derive = derive.trim();
+        if !all.contains(&derive) {
+            all.push(derive);
+        }
+    }
+    writeln!(w, "#[derive({})]", all.join(", ")).unwrap();
+    for attribute in configured.iter().flat_map(|it| &it.attributes) {
+        writeln!(w, "{attribute}").unwrap();
+    }
+}
+
+/// Path of a query module from the `queries` module, like `billing::invoices`.
+fn module_path(info: &ModuleInfo) -> String {
+    info.parents
+        .iter()
+        .chain(std::iter::once(&info.name))
+        .map(String::as_str)
+        .collect::<Vec<_>>()
+        .join("::")
+}
+
+/// Derives configured for the struct `name` of `module`, by bare and by qualified name.
+fn struct_derives<'a>(
+    derives: &'a DeriveSettings,
+    module: &str,
+    name: &str,
+) -> Vec<&'a TypeDerives> {
+    let qualified = format!("{module}::{name}");
+    [name, qualified.as_str()]
+        .into_iter()
+        .filter_map(|key| derives.structs.get(key))
+        .collect()
+}
+
+/// Generates a named params struct. Global derives and attributes are not applied, as params
+/// structs are generic and borrow their fields: they only get the ones configured for them.
+fn gen_params_struct(
+    w: &mut impl Write,
+    params: &PreparedItem,
+    configured: &[&TypeDerives],
+    settings: &CodegenSettings,
+) {
     let PreparedItem {
         name,
         fields,
         is_copy,
         is_named,
         is_ref,
+        doc,
     } = params;
     let is_async = settings.is_async;
     if *is_named {
         let traits = &mut Vec::new();
 
-        let copy = if *is_copy { "Clone,Copy," } else { "" };
+        let builtin: &[&str] = if *is_copy {
+            &["Clone", "Copy", "Debug"]
+        } else {
+            &["Debug"]
+        };
         let lifetime = if *is_ref { "'a," } else { "" };
         let fields_ty = fields
             .iter()
@@ -296,8 +433,9 @@ fn gen_params_struct(w: &mut impl Write, params: &PreparedItem, settings: Codege
             .collect::<Vec<_>>();
         let fields_name = fields.iter().map(|p| &p.name);
         let traits_idx = (1..=traits.len()).into_iter().map(idx_char);
+        gen_doc(w, doc);
+        gen_derives(w, builtin, configured);
         code!(w =>
-            #[derive($copy Debug)]
             pub struct $name<$lifetime $($traits_idx: $traits,)> {
                 $(pub $fields_name: $fields_ty,)
             }
@@ -308,28 +446,38 @@ fn gen_params_struct(w: &mut impl Write, params: &PreparedItem, settings: Codege
 fn gen_row_structs(
     w: &mut impl Write,
     row: &PreparedItem,
-    CodegenSettings {
+    configured: &[&TypeDerives],
+    &CodegenSettings {
         is_async,
         derive_ser,
-    }: CodegenSettings,
+        ..
+    }: &CodegenSettings,
 ) {
     let PreparedItem {
         name,
         fields,
         is_copy,
         is_named,
+        doc,
         ..
     } = row;
     if *is_named {
         // Generate row struct
         let fields_name = fields.iter().map(|p| &p.name);
         let fields_ty = fields.iter().map(|p| p.own_struct());
-        let copy = if *is_copy { "Copy" } else { "" };
-        let ser_str = if derive_ser { "serde::Serialize," } else { "" };
+        let fields_doc = fields.iter().map(|p| doc_string(&p.doc));
+        let mut builtin = vec!["Debug", "Clone", "PartialEq"];
+        if derive_ser {
+            builtin.insert(0, "serde::Serialize");
+        }
+        if *is_copy {
+            builtin.push("Copy");
+        }
+        gen_doc(w, doc);
+        gen_derives(w, &builtin, configured);
         code!(w =>
-            #[derive($ser_str Debug, Clone, PartialEq,$copy)]
             pub struct $name {
-                $(pub $fields_name : $fields_ty,)
+                $($fields_doc pub $fields_name : $fields_ty,)
             }
         );
 
@@ -450,13 +598,17 @@ fn gen_query_fn<W: Write>(
     w: &mut W,
     module: &PreparedModule,
     query: &PreparedQuery,
-    CodegenSettings { is_async, .. }: CodegenSettings,
+    &CodegenSettings { is_async, .. }: &CodegenSettings,
 ) {
     let PreparedQuery {
         name,
         row,
         sql,
+        source_sql,
+        doc,
         param,
+        deprecated,
+        attributes,
     } = query;
 
     let (client_mut, fn_async, fn_await, backend, client) = if is_async {
@@ -542,17 +694,38 @@ fn gen_query_fn<W: Write>(
     };
     // Gen statement struct
     {
-        let sql = sql.replace('"', "\\\""); // Rust string format escaping
+        // Rust string format escaping
+        let sql = sql.replace('\\', "\\\\").replace('"', "\\\"");
         let name = escape_keyword(name.clone());
+        gen_query_doc(w, doc, source_sql);
+        match deprecated {
+            Some(Some(note)) => writeln!(w, "#[deprecated = {note:?}]").unwrap(),
+            Some(None) => writeln!(w, "#[deprecated]").unwrap(),
+            None => {}
+        }
         code!(w =>
             pub fn $name() -> ${struct_name}Stmt {
                 ${struct_name}Stmt($client::private::Stmt::new("$sql"))
             }
+        );
+        gen_doc(w, doc);
+        code!(w =>
             pub struct ${struct_name}Stmt($client::private::Stmt);
             impl ${struct_name}Stmt {
                 $!lazy_impl
             }
         );
+        // Attributes unknown to Cornucopia are left to the user code
+        if !attributes.is_empty() {
+            let attributes = attributes
+                .iter()
+                .map(|(name, value)| format!("({name:?}, {value:?})"));
+            code!(w =>
+                impl ${struct_name}Stmt {
+                    pub const ATTRIBUTES: &'static [(&'static str, Option<&'static str>)] = &[$($attributes,)];
+                }
+            );
+        }
     }
 
     // Param impl
@@ -611,10 +784,11 @@ fn gen_custom_type(
     w: &mut impl Write,
     schema: &str,
     prepared: &PreparedType,
-    CodegenSettings {
+    &CodegenSettings {
         derive_ser,
         is_async,
-    }: CodegenSettings,
+        ref derives,
+    }: &CodegenSettings,
 ) {
     let PreparedType {
         struct_name,
@@ -622,13 +796,22 @@ fn gen_custom_type(
         is_copy,
         is_params,
         name,
+        doc,
     } = prepared;
-    let copy = if *is_copy { "Copy," } else { "" };
-    let ser_str = if derive_ser { "serde::Serialize," } else { "" };
+    let ser: &[&str] = if derive_ser {
+        &["serde::Serialize"]
+    } else {
+        &[]
+    };
+    let configured: Vec<_> = std::iter::once(&derives.global)
+        .chain(derives.types.get(&format!("{schema}.{name}")))
+        .collect();
     match content {
         PreparedContent::Enum(variants) => {
+            let builtin = [ser, &["Debug", "Clone", "Copy", "PartialEq", "Eq"]].concat();
+            gen_doc(w, doc);
+            gen_derives(w, &builtin, &configured);
             code!(w =>
-                #[derive($ser_str Debug, Clone, Copy, PartialEq, Eq)]
                 #[allow(non_camel_case_types)]
                 pub enum $struct_name {
                     $($variants,)
@@ -640,11 +823,21 @@ fn gen_custom_type(
             let fields_name = fields.iter().map(|p| &p.name);
             {
                 let fields_ty = fields.iter().map(|p| p.own_struct());
+                let fields_doc = fields.iter().map(|p| doc_string(&p.doc));
+                let copy: &[&str] = if *is_copy { &["Copy"] } else { &[] };
+                let builtin = [
+                    ser,
+                    &["Debug", "postgres_types::FromSql"],
+                    copy,
+                    &["Clone", "PartialEq"],
+                ]
+                .concat();
+                gen_doc(w, doc);
+                gen_derives(w, &builtin, &configured);
                 code!(w =>
-                    #[derive($ser_str Debug,postgres_types::FromSql,$copy Clone, PartialEq)]
                     #[postgres(name = "$name")]
                     pub struct $struct_name {
-                        $(pub $fields_name: $fields_ty,)
+                        $($fields_doc pub $fields_name: $fields_ty,)
                     }
                 );
             }
@@ -687,81 +880,301 @@ fn gen_custom_type(
     }
 }
 
-fn gen_type_modules<W: Write>(
-    w: &mut W,
+fn gen_type_modules(
     prepared: &IndexMap<String, Vec<PreparedType>>,
-    settings: CodegenSettings,
-) {
-    let modules = prepared.iter().map(|(schema, types)| {
-        move |w: &mut W| {
-            let lazy = |w: &mut W| {
-                for ty in types {
-                    gen_custom_type(w, schema, ty, settings)
-                }
-            };
+    settings: &CodegenSettings,
+) -> GeneratedModule {
+    let modules = prepared
+        .iter()
+        .map(|(schema, types)| {
+            let mut content = String::new();
+            for ty in types {
+                gen_custom_type(&mut content, schema, ty, settings)
+            }
+            GeneratedModule::new(schema, "", content, Vec::new())
+        })
+        .collect();
+    GeneratedModule::new("types", ALLOW_ATTRIBUTES, String::new(), modules)
+}
 
-            code!(w =>
-            pub mod $schema {
-                $!lazy
-            });
+/// Generates the query modules at `depth`, nesting the modules located in subdirectories.
+/// `modules` must be sorted, and share the same parents up to `depth`.
+fn gen_query_modules(
+    modules: &[PreparedModule],
+    depth: usize,
+    import: &str,
+    settings: &CodegenSettings,
+) -> Vec<GeneratedModule> {
+    let mut generated = Vec::new();
+    let mut remaining = modules;
+    while let Some(first) = remaining.first() {
+        if let Some(parent) = first.info.parents.get(depth) {
+            // Group all modules located in this subdirectory
+            let len = remaining
+                .iter()
+                .take_while(|module| module.info.parents.get(depth) == Some(parent))
+                .count();
+            let (group, rest) = remaining.split_at(len);
+            // Types are accessed with `super::super::types` from query modules
+            generated.push(GeneratedModule::new(
+                parent,
+                "",
+                String::from("use super::types;"),
+                gen_query_modules(group, depth + 1, import, settings),
+            ));
+            remaining = rest;
+        } else {
+            generated.push(gen_query_module(first, import, settings));
+            remaining = &remaining[1..];
         }
+    }
+    generated
+}
+
+fn gen_query_module(
+    module: &PreparedModule,
+    import: &str,
+    settings: &CodegenSettings,
+) -> GeneratedModule {
+    let path = module_path(&module.info);
+    let derives = &settings.derives;
+    let params_string = module.params.values().map(|params| {
+        let configured = struct_derives(derives, &path, &params.name.value);
+        move |w: &mut String| gen_params_struct(w, params, &configured, settings)
+    });
+    let rows_string = module.rows.values().map(|row| {
+        let configured: Vec<_> = std::iter::once(&derives.global)
+            .chain(struct_derives(derives, &path, &row.name.value))
+            .collect();
+        move |w: &mut String| gen_row_structs(w, row, &configured, settings)
     });
+    let queries_string = module
+        .queries
+        .values()
+        .map(|query| |w: &mut String| gen_query_fn(w, module, query, settings));
+
+    let mut content = String::new();
+    let w = &mut content;
     code!(w =>
-        #[allow(clippy::all, clippy::pedantic)]
-        #[allow(unused_variables)]
-        #[allow(unused_imports)]
-        #[allow(dead_code)]
-        pub mod types {
-            $($!modules)
-        }
+        $import
+        $($!params_string)
+        $($!rows_string)
+        $($!queries_string)
     );
+    GeneratedModule::new(&module.info.name, "", content, Vec::new())
+}
+
+/// Checks that every type and struct with configured derives is generated.
+fn check_derives(preparation: &Preparation, derives: &DeriveSettings) -> Vec<Error> {
+    let mut errors = Vec::new();
+    for key in derives.types.keys() {
+        let generated = preparation.types.iter().any(|(schema, types)| {
+            types
+                .iter()
+                .any(|ty| key.split_once('.') == Some((schema, &ty.name)))
+        });
+        if !generated {
+            errors.push(Error::UnknownType { key: key.clone() });
+        }
+    }
+    for key in derives.structs.keys() {
+        let generated = preparation.modules.iter().any(|module| {
+            let path = module_path(&module.info);
+            let items = module.rows.values().chain(module.params.values());
+            items
+                .filter(|it| it.is_named)
+                .any(|it| *key == it.name.value || *key == format!("{path}::{}", it.name))
+        });
+        if !generated {
+            errors.push(Error::UnknownStruct { key: key.clone() });
+        }
+    }
+    errors
 }
 
-pub(crate) fn generate(preparation: Preparation, settings: CodegenSettings) -> String {
+pub(crate) fn generate(
+    preparation: Preparation,
+    settings: &CodegenSettings,
+) -> Result<GeneratedCode, Vec<Error>> {
+    let errors = check_derives(&preparation, &settings.derives);
+    if !errors.is_empty() {
+        return Err(errors);
+    }
     let import = if settings.is_async {
         "use futures::{{StreamExt, TryStreamExt}};use futures; use cornucopia_async::GenericClient;"
     } else {
         "use postgres::{{fallible_iterator::FallibleIterator,GenericClient}};"
     };
-    let mut buff = "// This file was generated with `cornucopia`. Do not modify.\n\n".to_string();
-    let w = &mut buff;
     // Generate database type
-    gen_type_modules(w, &preparation.types, settings);
+    let types = gen_type_modules(&preparation.types, settings);
     // Generate queries
-    let query_modules = preparation.modules.iter().map(|module| {
-        move |w: &mut String| {
-            let name = &module.info.name;
-            let params_string = module
-                .params
-                .values()
-                .map(|params| |w: &mut String| gen_params_struct(w, params, settings));
-            let rows_string = module
-                .rows
-                .values()
-                .map(|row| |w: &mut String| gen_row_structs(w, row, settings));
-            let queries_string = module
-                .queries
-                .values()
-                .map(|query| |w: &mut String| gen_query_fn(w, module, query, settings));
+    let has_parents = preparation
+        .modules
+        .iter()
+        .any(|module| !module.info.parents.is_empty());
+    let types_import = if has_parents { "use super::types;" } else { "" };
+    let queries = GeneratedModule::new(
+        "queries",
+        ALLOW_ATTRIBUTES,
+        String::from(types_import),
+        gen_query_modules(&preparation.modules, 0, import, settings),
+    );
+    Ok(GeneratedCode {
+        modules: vec![types, queries],
+        warnings: preparation
+            .modules
+            .iter()
+            .flat_map(|module| module.warnings.iter().cloned())
+            .collect(),
+    })
+}
 
-            code!(w =>
-                pub mod $name {
-                    $import
-                    $($!params_string)
-                    $($!rows_string)
-                    $($!queries_string)
-                }
-            );
+/// First line of every generated file.
+pub(crate) const HEADER: &str = "// This file was generated with `cornucopia`. Do not modify.\n\n";
+
+const ALLOW_ATTRIBUTES: &str = "#[allow(clippy::all, clippy::pedantic)]
+#[allow(unused_variables)]
+#[allow(unused_imports)]
+#[allow(dead_code)]";
+
+/// Code generated by Cornucopia, which can be rendered as a single file or
+/// as a directory containing one file per module.
+#[derive(Debug, Clone)]
+pub struct GeneratedCode {
+    modules: Vec<GeneratedModule>,
+    warnings: Vec<Warning>,
+}
+
+// Warnings are not part of the code
+impl PartialEq for GeneratedCode {
+    fn eq(&self, other: &Self) -> bool {
+        self.modules == other.modules
+    }
+}
+
+impl Eq for GeneratedCode {}
+
+impl GeneratedCode {
+    /// Non-fatal diagnostics reported while preparing the queries.
+    pub fn warnings(&self) -> &[Warning] {
+        &self.warnings
+    }
+
+    /// Renders all modules inline, in a single file.
+    pub fn to_single_file(&self) -> String {
+        let mut w = String::from(HEADER);
+        for module in &self.modules {
+            module.write_inline(&mut w);
         }
-    });
-    code!(w =>
-        #[allow(clippy::all, clippy::pedantic)]
-        #[allow(unused_variables)]
-        #[allow(unused_imports)]
-        #[allow(dead_code)]
-        pub mod queries {
-            $($!query_modules)
+        w
+    }
+
+    /// Renders one file per module, with paths relative to the destination directory.
+    /// The root module is `mod.rs`.
+    pub fn to_files(&self) -> Vec<(PathBuf, String)> {
+        let mut root = String::from(HEADER);
+        let mut files = Vec::new();
+        for module in &self.modules {
+            module.write_declaration(&mut root);
+            module.write_files(Path::new(""), &mut files);
         }
-    );
-    buff
+        files.push((PathBuf::from("mod.rs"), root));
+        files.sort();
+        files
+    }
+}
+
+impl Display for GeneratedCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.to_single_file())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct GeneratedModule {
+    name: String,
+    attributes: &'static str,
+    content: String,
+    children: Vec<GeneratedModule>,
+}
+
+impl GeneratedModule {
+    fn new(
+        name: &str,
+        attributes: &'static str,
+        content: String,
+        children: Vec<GeneratedModule>,
+    ) -> Self {
+        Self {
+            name: name.to_string(),
+            attributes,
+            content,
+            children,
+        }
+    }
+
+    fn write_inline(&self, w: &mut String) {
+        let GeneratedModule {
+            name,
+            attributes,
+            content,
+            children,
+        } = self;
+        let children = children
+            .iter()
+            .map(|child| |w: &mut String| child.write_inline(w));
+        code!(w =>
+            $attributes
+            pub mod $name {
+                $content
+                $($!children)
+            }
+        );
+    }
+
+    fn write_declaration(&self, w: &mut String) {
+        let GeneratedModule {
+            name, attributes, ..
+        } = self;
+        code!(w =>
+            $attributes
+            pub mod $name;
+        );
+    }
+
+    /// Modules with children are written as `name/mod.rs`, others as `name.rs`
+    fn write_files(&self, dir: &Path, files: &mut Vec<(PathBuf, String)>) {
+        let mut file = String::from(HEADER);
+        file.push_str(&self.content);
+        let path = if self.children.is_empty() {
+            dir.join(format!("{}.rs", self.name))
+        } else {
+            let dir = dir.join(&self.name);
+            for child in &self.children {
+                child.write_declaration(&mut file);
+                child.write_files(&dir, files);
+            }
+            dir.join("mod.rs")
+        };
+        files.push((path, file));
+    }
+}
+
+pub(crate) mod error {
+    use miette::Diagnostic;
+    use thiserror::Error as ThisError;
+
+    #[derive(Debug, ThisError, Diagnostic)]
+    pub enum Error {
+        #[error("No generated type matches `{key}`, which has derives configured")]
+        #[diagnostic(help(
+            "use the schema-qualified name of a Postgres type used by your queries, like `public.mood`"
+        ))]
+        UnknownType { key: String },
+        #[error("No generated row or params struct matches `{key}`, which has derives configured")]
+        #[diagnostic(help(
+            "use the name of a generated struct, optionally qualified by its module like `billing::Row`"
+        ))]
+        UnknownStruct { key: String },
+    }
 }
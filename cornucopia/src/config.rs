@@ -0,0 +1,158 @@
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use serde::Deserialize;
+
+use crate::TypeDerives;
+
+use self::error::Error;
+
+/// Name of the configuration file, discovered from the working directory and its ancestors.
+pub(crate) const FILE_NAME: &str = "cornucopia.toml";
+pub(crate) const DEFAULT_QUERIES_PATH: &str = "queries/";
+pub(crate) const DEFAULT_DESTINATION: &str = "src/cornucopia.rs";
+
+/// Project settings read from `cornucopia.toml`. Every setting is optional,
+/// and command line flags take precedence over them.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub(crate) struct Config {
+    pub(crate) queries_path: Option<String>,
+    pub(crate) destination: Option<String>,
+    pub(crate) metadata: Option<String>,
+    pub(crate) sync: Option<bool>,
+    pub(crate) serialize: Option<bool>,
+    /// Derives added to every row struct and Postgres type
+    pub(crate) derives: Option<Vec<String>>,
+    /// Attributes added to every row struct and Postgres type
+    pub(crate) attributes: Option<Vec<String>>,
+    /// Derives and attributes of the types generated for Postgres types,
+    /// by schema-qualified name like `public.mood`
+    #[serde(default)]
+    pub(crate) types: BTreeMap<String, DerivesConfig>,
+    /// Derives and attributes of the row and params structs, by struct name like `Row`,
+    /// or qualified by its module like `billing::Row`
+    #[serde(default)]
+    pub(crate) structs: BTreeMap<String, DerivesConfig>,
+    pub(crate) deny_warnings: Option<bool>,
+    /// Postgres url of the database used by the `live` command
+    pub(crate) url: Option<String>,
+    /// SQL files used by the `schema` command
+    pub(crate) schema_files: Option<Vec<String>>,
+    pub(crate) local: Option<bool>,
+    #[serde(default)]
+    pub(crate) container: ContainerConfig,
+}
+
+/// Settings of the container used by the `schema` command.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub(crate) struct ContainerConfig {
+    pub(crate) runtime: Option<String>,
+    pub(crate) image: Option<String>,
+    pub(crate) tag: Option<String>,
+    pub(crate) name: Option<String>,
+    pub(crate) port: Option<u16>,
+    pub(crate) user: Option<String>,
+    pub(crate) password: Option<String>,
+    pub(crate) keep_alive: Option<bool>,
+}
+
+/// Derives and attributes of a generated type.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub(crate) struct DerivesConfig {
+    #[serde(default)]
+    pub(crate) derives: Vec<String>,
+    #[serde(default)]
+    pub(crate) attributes: Vec<String>,
+}
+
+impl From<DerivesConfig> for TypeDerives {
+    fn from(
+        DerivesConfig {
+            derives,
+            attributes,
+        }: DerivesConfig,
+    ) -> Self {
+        Self {
+            derives,
+            attributes,
+        }
+    }
+}
+
+impl Config {
+    /// Reads the configuration file at `path`, or the one discovered from the working directory
+    /// if `None`. Returns an empty configuration if no file is found.
+    pub(crate) fn load(path: Option<&str>) -> Result<Self, Error> {
+        let path = match path.map(PathBuf::from).or_else(discover) {
+            Some(path) => path,
+            None => return Ok(Self::default()),
+        };
+        let content = std::fs::read_to_string(&path).map_err(|err| Error::Io {
+            path: path.to_string_lossy().into_owned(),
+            err,
+        })?;
+        let mut config: Config = toml::from_str(&content).map_err(|err| Error::Parse {
+            path: path.to_string_lossy().into_owned(),
+            err,
+        })?;
+        // Paths are relative to the configuration file, including default ones
+        config
+            .queries_path
+            .get_or_insert_with(|| String::from(DEFAULT_QUERIES_PATH));
+        config
+            .destination
+            .get_or_insert_with(|| String::from(DEFAULT_DESTINATION));
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            config.rebase(dir);
+        }
+        Ok(config)
+    }
+
+    fn rebase(&mut self, dir: &Path) {
+        let join = |path: &mut String| *path = dir.join(&path).to_string_lossy().into_owned();
+        self.queries_path.iter_mut().for_each(join);
+        self.destination.iter_mut().for_each(join);
+        self.metadata.iter_mut().for_each(join);
+        self.schema_files.iter_mut().flatten().for_each(join);
+    }
+}
+
+/// Finds the closest configuration file, starting from the working directory.
+fn discover() -> Option<PathBuf> {
+    // Keep paths relative when the file is in the working directory
+    if Path::new(FILE_NAME).is_file() {
+        return Some(PathBuf::from(FILE_NAME));
+    }
+    std::env::current_dir()
+        .ok()?
+        .ancestors()
+        .map(|dir| dir.join(FILE_NAME))
+        .find(|path| path.is_file())
+}
+
+pub(crate) mod error {
+    use miette::Diagnostic;
+    use thiserror::Error as ThisError;
+
+    #[derive(Debug, ThisError, Diagnostic)]
+    pub enum Error {
+        #[error("Could not read configuration file `{path}`: ({err})")]
+        Io { path: String, err: std::io::Error },
+        #[error("Could not parse configuration file `{path}`: ({err})")]
+        Parse { path: String, err: toml::de::Error },
+        #[error("Missing `{setting}` for the `{command}` command")]
+        #[diagnostic(help("pass it on the command line or set `{setting}` in `cornucopia.toml`"))]
+        MissingSetting {
+            setting: &'static str,
+            command: &'static str,
+        },
+        #[error("No database to generate your queries against")]
+        #[diagnostic(help("use the `live`, `schema` or `offline` command, or set `url` or `schema-files` in `cornucopia.toml`"))]
+        MissingSource,
+    }
+}
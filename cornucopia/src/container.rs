@@ -1,66 +1,392 @@
 use std::process::{Command, Stdio};
 
+use postgres::{Client, Config, NoTls};
+
 use self::error::Error;
 
-/// Starts Cornucopia's database container and wait until it reports healthy.
-pub fn setup(podman: bool) -> Result<(), Error> {
-    spawn_container(podman)?;
-    healthcheck(podman, 120, 50)?;
+/// Settings of the database container managed by Cornucopia.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContainerSettings {
+    /// Container runtime binary, like `docker`, `podman` or `nerdctl`.
+    pub runtime: String,
+    /// Image used to start the container. It must behave like the official `postgres` image.
+    pub image: String,
+    /// Tag of the image.
+    pub tag: String,
+    /// Name of the container. A unique name is generated for each container if `None`,
+    /// unless `keep_alive` is set, in which case `cornucopia_postgres` is used.
+    pub name: Option<String>,
+    /// Host port on which the container is exposed. A free port is assigned by the OS if `None`.
+    pub port: Option<u16>,
+    /// PostgreSQL superuser created in the container.
+    pub user: String,
+    /// Password of the PostgreSQL superuser.
+    pub password: String,
+    /// Keep the container running after cleanup, so that it can be reused by the next setup.
+    /// Each setup then works in a fresh database, which is dropped on cleanup. An existing
+    /// container spawned with another image, tag, user or password is an error.
+    ///
+    /// The container must be stopped manually, e.g. `docker rm -f -v cornucopia_postgres`.
+    pub keep_alive: bool,
+}
+
+impl Default for ContainerSettings {
+    fn default() -> Self {
+        Self {
+            runtime: String::from("docker"),
+            image: String::from("postgres"),
+            tag: String::from("latest"),
+            name: None,
+            port: None,
+            user: String::from("postgres"),
+            password: String::from("postgres"),
+            keep_alive: false,
+        }
+    }
+}
+
+/// A running container started by [`setup`].
+#[derive(Debug)]
+pub struct Container {
+    settings: ContainerSettings,
+    name: String,
+    port: u16,
+    dbname: String,
+}
+
+impl Container {
+    /// Name of the container.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Host port on which the container is exposed.
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    /// PostgreSQL superuser of the container.
+    pub fn user(&self) -> &str {
+        &self.settings.user
+    }
+
+    /// Password of the PostgreSQL superuser.
+    pub fn password(&self) -> &str {
+        &self.settings.password
+    }
+
+    /// Database to work in.
+    pub fn dbname(&self) -> &str {
+        &self.dbname
+    }
+
+    /// Stop and remove this container and its volume.
+    /// In keep-alive mode, only drop this setup's database.
+    pub fn cleanup(self) -> Result<(), Error> {
+        if self.settings.keep_alive {
+            drop_database(&mut self.admin_conn()?, &self.dbname)
+                .map_err(|e| Error::database("drop database", e))?;
+        } else {
+            stop_container(&self.settings, &self.name)?;
+            remove_container(&self.settings, &self.name)?;
+        }
+        Ok(())
+    }
+
+    /// Connection settings to this container's database.
+    pub(crate) fn config(&self) -> Config {
+        let mut config = Config::new();
+        config
+            .user(&self.settings.user)
+            .password(&self.settings.password)
+            .host("127.0.0.1")
+            .port(self.port)
+            .dbname(&self.dbname);
+        config
+    }
+
+    /// Connects to the maintenance database, to create or drop databases.
+    fn admin_conn(&self) -> Result<Client, Error> {
+        self.config()
+            .dbname("postgres")
+            .connect(NoTls)
+            .map_err(|e| Error::database("connect", e))
+    }
+}
+
+/// Starts a database container and wait until it reports healthy.
+///
+/// Unless they are fixed by `settings`, the container gets a unique name and a free port,
+/// so that concurrent invocations never collide. If the container doesn't become healthy,
+/// it is removed before returning the error.
+///
+/// In keep-alive mode, a running container is reused and a fresh database is created in it.
+/// Databases left behind by interrupted runs are dropped then.
+pub fn setup(settings: &ContainerSettings) -> Result<Container, Error> {
+    if settings.keep_alive {
+        return setup_keep_alive(settings);
+    }
+    let name = settings
+        .name
+        .clone()
+        .unwrap_or_else(|| unique_name("cornucopia_postgres"));
+    if let Err(e) = spawn_container(settings, &name) {
+        // The container may have been created even if it couldn't start
+        remove_container(settings, &name).ok();
+        return Err(e);
+    }
+    let container: Result<Container, Error> = (|| {
+        let port = published_port(settings, &name)?;
+        healthcheck(settings, &name, 120, 50)?;
+        Ok(Container {
+            settings: settings.clone(),
+            name: name.clone(),
+            port,
+            dbname: String::from("postgres"),
+        })
+    })();
+    if container.is_err() {
+        stop_container(settings, &name).ok();
+        remove_container(settings, &name).ok();
+    }
+    container
+}
+
+/// Reuses the keep-alive container, starting it if needed, and creates a fresh database in it.
+fn setup_keep_alive(settings: &ContainerSettings) -> Result<Container, Error> {
+    let name = settings
+        .name
+        .clone()
+        .unwrap_or_else(|| String::from("cornucopia_postgres"));
+    match is_running(settings, &name) {
+        Some(true) => check_reused(settings, &name)?,
+        Some(false) => {
+            check_reused(settings, &name)?;
+            cmd(settings, &["start", &name], "start container")?;
+        }
+        None => {
+            // Another invocation may have spawned it concurrently
+            if let Err(e) = spawn_container(settings, &name) {
+                if is_running(settings, &name).is_none() {
+                    return Err(e);
+                }
+                check_reused(settings, &name)?;
+            }
+        }
+    }
+    if !is_postgres_healthy(settings, &name)? {
+        healthcheck(settings, &name, 120, 50)?;
+    }
+    let container = Container {
+        settings: settings.clone(),
+        port: published_port(settings, &name)?,
+        name,
+        dbname: unique_name(DATABASE_PREFIX),
+    };
+    let mut admin_conn = container.admin_conn()?;
+    drop_stale_databases(&mut admin_conn)
+        .map_err(|e| Error::database("drop stale databases", e))?;
+    admin_conn
+        .batch_execute(&format!("CREATE DATABASE \"{}\"", container.dbname))
+        .map_err(|e| Error::database("create database", e))?;
+    Ok(container)
+}
+
+/// Prefix of the databases created in the keep-alive container.
+pub(crate) const DATABASE_PREFIX: &str = "cornucopia";
+
+/// Age after which a database of the keep-alive container is considered left behind by
+/// an interrupted run.
+const STALE_DATABASE_SECS: u64 = 60 * 60;
+
+/// Drops the databases left behind by interrupted runs, which never cleaned up. Databases that
+/// are still in use are kept, and so are recent ones, which a concurrent run may not have
+/// connected to yet.
+fn drop_stale_databases(client: &mut Client) -> Result<(), postgres::Error> {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    let databases = client.query(
+        "SELECT datname FROM pg_database WHERE datname LIKE $1",
+        &[&format!("{DATABASE_PREFIX}\\_%")],
+    )?;
+    for row in databases {
+        let dbname: String = row.get(0);
+        let is_stale = unique_name_secs(&dbname)
+            .filter(|secs| now.saturating_sub(*secs) > STALE_DATABASE_SECS)
+            .is_some();
+        if is_stale {
+            // Without `FORCE`, a database in use is not dropped
+            client
+                .batch_execute(&format!("DROP DATABASE IF EXISTS \"{dbname}\""))
+                .ok();
+        }
+    }
     Ok(())
 }
 
-/// Stop and remove a container and its volume.
-pub fn cleanup(podman: bool) -> Result<(), Error> {
-    stop_container(podman)?;
-    remove_container(podman)?;
+/// Drops a database, even if connections to it are still closing.
+pub(crate) fn drop_database(client: &mut Client, dbname: &str) -> Result<(), postgres::Error> {
+    let version: String = client.query_one("SHOW server_version_num", &[])?.get(0);
+    if matches!(version.parse::<u32>(), Ok(version) if version >= 130_000) {
+        client.batch_execute(&format!(
+            "DROP DATABASE IF EXISTS \"{dbname}\" WITH (FORCE)"
+        ))
+    } else {
+        // `FORCE` requires PostgreSQL 13
+        client.execute(
+            "SELECT pg_terminate_backend(pid) FROM pg_stat_activity WHERE datname = $1",
+            &[&dbname],
+        )?;
+        client.batch_execute(&format!("DROP DATABASE IF EXISTS \"{dbname}\""))
+    }
+}
+
+/// Generates a name unique to this invocation, holding its creation time.
+pub(crate) fn unique_name(prefix: &str) -> String {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default();
+    format!(
+        "{prefix}_{}_{}_{}",
+        std::process::id(),
+        now.as_secs(),
+        now.subsec_nanos()
+    )
+}
+
+/// Creation time of a name generated by [`unique_name`], in seconds since the Unix epoch.
+fn unique_name_secs(name: &str) -> Option<u64> {
+    name.rsplit('_').nth(1)?.parse().ok()
+}
+
+/// Checks if a container exists and is running. Returns `None` if it doesn't exist.
+fn is_running(settings: &ContainerSettings, name: &str) -> Option<bool> {
+    cmd(
+        settings,
+        &["inspect", "--format", "{{.State.Running}}", name],
+        "inspect container",
+    )
+    .ok()
+    .map(|output| output.trim() == "true")
+}
+
+/// Checks that an existing keep-alive container was spawned with the same image, tag,
+/// user and password as `settings`, as it would otherwise be reused silently.
+fn check_reused(settings: &ContainerSettings, name: &str) -> Result<(), Error> {
+    let output = cmd(
+        settings,
+        &[
+            "inspect",
+            "--format",
+            "{{.Config.Image}}{{range .Config.Env}}\n{{.}}{{end}}",
+            name,
+        ],
+        "inspect container",
+    )?;
+    let mut lines = output.lines();
+    let image = lines.next().unwrap_or_default().trim();
+    let env: Vec<_> = lines.map(str::trim).collect();
+    let expected_image = format!("{}:{}", settings.image, settings.tag);
+    // Podman records the fully qualified image, like `docker.io/library/postgres:latest`
+    if image != expected_image && !image.ends_with(&format!("/{expected_image}")) {
+        return Err(Error::mismatch(
+            name,
+            format!("runs the image `{image}` instead of `{expected_image}`"),
+            settings,
+        ));
+    }
+    for (var, value) in [
+        ("POSTGRES_USER", &settings.user),
+        ("POSTGRES_PASSWORD", &settings.password),
+    ] {
+        if !env.contains(&format!("{var}={value}").as_str()) {
+            return Err(Error::mismatch(
+                name,
+                format!("was not spawned with the configured `{var}`"),
+                settings,
+            ));
+        }
+    }
     Ok(())
 }
 
 /// Starts Cornucopia's database container.
-fn spawn_container(podman: bool) -> Result<(), Error> {
+fn spawn_container(settings: &ContainerSettings, name: &str) -> Result<(), Error> {
+    let publish = match settings.port {
+        Some(port) => format!("{port}:5432"),
+        None => String::from("127.0.0.1::5432"),
+    };
     cmd(
-        podman,
+        settings,
         &[
             "run",
             "-d",
             "--name",
-            "cornucopia_postgres",
+            name,
             "-p",
-            "5435:5432",
+            &publish,
             "-e",
-            "POSTGRES_PASSWORD=postgres",
-            "postgres",
+            &format!("POSTGRES_USER={}", settings.user),
+            "-e",
+            &format!("POSTGRES_PASSWORD={}", settings.password),
+            &format!("{}:{}", settings.image, settings.tag),
         ],
         "spawn container",
-    )
+    )?;
+    Ok(())
+}
+
+/// Retrieves the host port to which the container's PostgreSQL port is published.
+fn published_port(settings: &ContainerSettings, name: &str) -> Result<u16, Error> {
+    if let Some(port) = settings.port {
+        return Ok(port);
+    }
+    let output = cmd(settings, &["port", name, "5432"], "retrieve container port")?;
+    // The output looks like `127.0.0.1:49153`, possibly with one line per address
+    output
+        .lines()
+        .find_map(|line| line.trim().rsplit(':').next()?.parse().ok())
+        .ok_or_else(|| {
+            Error::new(
+                format!("Couldn't parse the port of container `{name}` from `{output}`"),
+                settings,
+            )
+        })
 }
 
 /// Checks if Cornucopia's container reports healthy
-fn is_postgres_healthy(podman: bool) -> Result<bool, Error> {
+fn is_postgres_healthy(settings: &ContainerSettings, name: &str) -> Result<bool, Error> {
     Ok(cmd(
-        podman,
-        &["exec", "cornucopia_postgres", "pg_isready"],
+        settings,
+        &["exec", name, "pg_isready", "-U", &settings.user],
         "check container health",
     )
     .is_ok())
 }
 
 /// This function controls how the healthcheck retries are handled.
-fn healthcheck(podman: bool, max_retries: u64, ms_per_retry: u64) -> Result<(), Error> {
+fn healthcheck(
+    settings: &ContainerSettings,
+    name: &str,
+    max_retries: u64,
+    ms_per_retry: u64,
+) -> Result<(), Error> {
     let slow_threshold = 10 + max_retries / 10;
     let mut nb_retries = 0;
-    while !is_postgres_healthy(podman)? {
+    while !is_postgres_healthy(settings, name)? {
         if nb_retries >= max_retries {
             return Err(Error::new(
                 String::from("Cornucopia reached the max number of connection retries"),
-                podman,
+                settings,
             ));
         };
         std::thread::sleep(std::time::Duration::from_millis(ms_per_retry));
         nb_retries += 1;
 
         if nb_retries % slow_threshold == 0 {
-            println!("Container startup slower than expected ({nb_retries} retries out of {max_retries})");
+            eprintln!("Container startup slower than expected ({nb_retries} retries out of {max_retries})");
         }
     }
     // Just for extra safety...
@@ -69,34 +395,33 @@ fn healthcheck(podman: bool, max_retries: u64, ms_per_retry: u64) -> Result<(),
 }
 
 /// Stops Cornucopia's container.
-fn stop_container(podman: bool) -> Result<(), Error> {
-    cmd(podman, &["stop", "cornucopia_postgres"], "stop container")
+fn stop_container(settings: &ContainerSettings, name: &str) -> Result<(), Error> {
+    cmd(settings, &["stop", name], "stop container")?;
+    Ok(())
 }
 
 /// Removes Cornucopia's container and its volume.
-fn remove_container(podman: bool) -> Result<(), Error> {
-    cmd(
-        podman,
-        &["rm", "-v", "cornucopia_postgres"],
-        "remove container",
-    )
+fn remove_container(settings: &ContainerSettings, name: &str) -> Result<(), Error> {
+    cmd(settings, &["rm", "-v", name], "remove container")?;
+    Ok(())
 }
 
-fn cmd(podman: bool, args: &[&'static str], action: &'static str) -> Result<(), Error> {
-    let command = if podman { "podman" } else { "docker" };
+/// Runs the container runtime with `args`, returning its standard output.
+fn cmd(settings: &ContainerSettings, args: &[&str], action: &'static str) -> Result<String, Error> {
+    let command = &settings.runtime;
     let output = Command::new(command)
         .args(args)
         .stderr(Stdio::piped())
-        .stdout(Stdio::null())
+        .stdout(Stdio::piped())
         .output()?;
 
     if output.status.success() {
-        Ok(())
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
     } else {
         let err = String::from_utf8_lossy(&output.stderr);
         Err(Error::new(
             format!("`{command}` couldn't {action}: {err}"),
-            podman,
+            settings,
         ))
     }
 }
@@ -107,6 +432,8 @@ pub(crate) mod error {
     use miette::Diagnostic;
     use thiserror::Error as ThisError;
 
+    use super::ContainerSettings;
+
     #[derive(Debug, ThisError, Diagnostic)]
     #[error("{msg}")]
     pub struct Error {
@@ -116,15 +443,51 @@ pub(crate) mod error {
     }
 
     impl Error {
-        pub fn new(msg: String, podman: bool) -> Self {
-            let help = if podman {
-                "Make sure that port 5435 is usable and that no container named `cornucopia_postgres` already exists."
+        pub fn new(msg: String, settings: &ContainerSettings) -> Self {
+            let ContainerSettings {
+                runtime,
+                name,
+                port,
+                keep_alive,
+                ..
+            } = settings;
+            let mut help = if runtime == "docker" {
+                String::from("Check that the docker daemon is up-and-running.")
             } else {
-                "First, check that the docker daemon is up-and-running. Then, make sure that port 5435 is usable and that no container named `cornucopia_postgres` already exists."
+                format!("Check that `{runtime}` is installed and usable.")
             };
+            if let Some(port) = port {
+                help.push_str(&format!(" Make sure that port {port} is usable."));
+            }
+            if let (Some(name), false) = (name, keep_alive) {
+                help.push_str(&format!(
+                    " Make sure that no container named `{name}` already exists."
+                ));
+            }
             Error {
                 msg,
-                help: Some(String::from(help)),
+                help: Some(help),
+            }
+        }
+    }
+
+    impl Error {
+        pub fn database(action: &'static str, e: postgres::Error) -> Self {
+            Error {
+                msg: format!("Couldn't {action} in the keep-alive container: {e:#}"),
+                help: None,
+            }
+        }
+    }
+
+    impl Error {
+        pub fn mismatch(name: &str, reason: String, settings: &ContainerSettings) -> Self {
+            Error {
+                msg: format!("The keep-alive container `{name}` {reason}"),
+                help: Some(format!(
+                    "Remove it with `{} rm -f -v {name}`, or set another container name.",
+                    settings.runtime
+                )),
             }
         }
     }
@@ -0,0 +1,83 @@
+use std::process::{Command, Stdio};
+
+use crate::conn;
+
+use self::error::Error;
+
+/// Snapshots the schema of a live database with the `pg_dump` binary from your `PATH`.
+///
+/// `url` is a URL or a `key=value` connection string, defaulting to `DATABASE_URL`. Missing
+/// parameters are read by `pg_dump` from the `PG*` environment variables, the service file
+/// and the password file. A password given in `url` is passed to `pg_dump` through
+/// `PGPASSWORD`, to keep it off its command line. Owners, privileges and everything unrelated
+/// to codegen are left out, as are the lines changing between runs, so that the same schema
+/// always gives the same snapshot.
+/// The snapshot can be loaded with [`load_schema`](crate::load_schema).
+pub fn dump_schema(url: Option<&str>) -> Result<String, Error> {
+    let mut command = Command::new("pg_dump");
+    command.args([
+        "--schema-only",
+        "--no-owner",
+        "--no-privileges",
+        "--no-tablespaces",
+        "--no-security-labels",
+        "--no-publications",
+        "--no-subscriptions",
+    ]);
+    if let Some(url) = url
+        .map(String::from)
+        .or_else(|| std::env::var("DATABASE_URL").ok())
+    {
+        // Arguments are visible to the other users of the machine, unlike the environment
+        let (url, password) = conn::split_password(&url);
+        command.arg("--dbname").arg(url);
+        if let Some(password) = password {
+            command.env("PGPASSWORD", password);
+        }
+    }
+    let output = command
+        .stdin(Stdio::null())
+        .stderr(Stdio::piped())
+        .stdout(Stdio::piped())
+        .output()
+        .map_err(Error::Spawn)?;
+    if !output.status.success() {
+        return Err(Error::Dump(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+
+    let dump = String::from_utf8_lossy(&output.stdout);
+    let mut snapshot = String::with_capacity(dump.len());
+    for line in dump.lines() {
+        // Versions depend on the machine, and restrict keys are random
+        let is_unstable = line.starts_with("-- Dumped from database version")
+            || line.starts_with("-- Dumped by pg_dump version")
+            || line.starts_with("\\restrict ")
+            || line.starts_with("\\unrestrict ");
+        if !is_unstable {
+            snapshot.push_str(line);
+            snapshot.push('\n');
+        }
+    }
+    Ok(snapshot)
+}
+
+pub(crate) mod error {
+    use miette::Diagnostic;
+    use thiserror::Error as ThisError;
+
+    #[derive(Debug, ThisError, Diagnostic)]
+    pub enum Error {
+        #[error("Could not run `pg_dump`: ({0})")]
+        #[diagnostic(help("make sure that PostgreSQL's `pg_dump` binary is in your `PATH`"))]
+        Spawn(std::io::Error),
+        #[error("Could not dump the database schema: {0}")]
+        #[diagnostic(help(
+            "`pg_dump` connects like `psql`, and must be at least as recent as the database server"
+        ))]
+        Dump(String),
+        #[error("Could not write schema snapshot to `{path}`: ({err})")]
+        Write { path: String, err: std::io::Error },
+    }
+}
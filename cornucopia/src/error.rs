@@ -1,11 +1,15 @@
 use miette::{Diagnostic, GraphicalReportHandler, GraphicalTheme};
 use thiserror::Error as ThisError;
 
+use crate::message_format::render_json;
+
 /// Enumeration of all the errors reported by Cornucopia.
 #[derive(Debug, ThisError, Diagnostic)]
 #[error(transparent)]
 #[diagnostic(transparent)]
 pub enum Error {
+    /// An error while reading the configuration file, or a missing setting.
+    Config(#[from] crate::config::error::Error),
     /// An error while trying to connect to a database.
     Connection(#[from] crate::conn::error::Error),
     /// An error while trying to read PostgreSQL query files.
@@ -16,24 +20,68 @@ pub enum Error {
     ValidateQueries(#[from] Box<crate::validation::error::Error>),
     /// An error while manipulating a container managed by Cornucopia.
     Container(#[from] crate::container::error::Error),
+    /// An error while manipulating a local server managed by Cornucopia.
+    Local(#[from] crate::local::error::Error),
     /// An error while trying to prepare PostgreSQL queries.
     PrepareQueries(#[from] crate::prepare_queries::error::Error),
+    /// An error while generating the code of prepared queries.
+    Codegen(#[from] crate::codegen::error::Error),
     /// An error while reading PostgreSQL schema files.
     LoadSchema(#[from] crate::load_schema::error::Error),
+    /// An error while snapshotting the schema of a live database.
+    DumpSchema(#[from] crate::dump_schema::error::Error),
+    /// An error while looking up the explanation of a code.
+    Explain(#[from] crate::explain::error::Error),
+    /// An error while reading or writing prepared queries metadata.
+    Metadata(#[from] crate::metadata::error::Error),
     /// An error while trying to write the generated code to its destination file.
     WriteCodeGenFile(#[from] WriteOutputError),
+    /// The generated code differs from the content of its destination file.
+    OutdatedCodeGenFile(#[from] OutdatedOutputError),
+    /// An error while watching files for changes.
+    Watch(#[from] crate::watch::error::Error),
+    /// An error while formatting query files.
+    Fmt(#[from] crate::format_queries::error::Error),
+    /// An error while serving the language server protocol.
+    Lsp(#[from] crate::lsp::error::Error),
+    /// Several errors found in the same run, reported together.
+    Multiple(#[from] MultipleErrors),
+    /// Warnings were reported while they are denied.
+    DeniedWarnings(#[from] DeniedWarningsError),
 }
 
 impl Error {
+    /// Reports `errors` together, or alone when there is a single one.
+    pub(crate) fn from_many<E: Into<Error>>(errors: Vec<E>) -> Self {
+        let mut errors: Vec<Error> = errors.into_iter().map(Into::into).collect();
+        if errors.len() == 1 {
+            errors.remove(0)
+        } else {
+            MultipleErrors { errors }.into()
+        }
+    }
+
     #[must_use]
     pub fn report(self) -> String {
-        let mut buff = String::new();
-        GraphicalReportHandler::new()
-            .with_theme(GraphicalTheme::unicode_nocolor())
-            .render_report(&mut buff, &self)
-            .unwrap();
-        buff
+        render_report(&self)
     }
+
+    /// Renders this error as JSON lines, one for each error it groups.
+    /// See `--message-format json` for the format.
+    #[must_use]
+    pub fn report_json(&self) -> String {
+        render_json(self)
+    }
+}
+
+/// Renders `diagnostic` without colors.
+pub(crate) fn render_report(diagnostic: &dyn Diagnostic) -> String {
+    let mut buff = String::new();
+    GraphicalReportHandler::new()
+        .with_theme(GraphicalTheme::unicode_nocolor())
+        .render_report(&mut buff, diagnostic)
+        .unwrap();
+    buff
 }
 
 #[derive(Debug, ThisError, Diagnostic)]
@@ -42,3 +90,26 @@ pub struct WriteOutputError {
     pub(crate) file_path: String,
     pub(crate) err: std::io::Error,
 }
+
+#[derive(Debug, ThisError, Diagnostic)]
+#[error("Generated code at `{file_path}` is outdated")]
+#[diagnostic(help("regenerate it by running the same command without `check`"))]
+pub struct OutdatedOutputError {
+    pub(crate) file_path: String,
+}
+
+#[derive(Debug, ThisError, Diagnostic)]
+#[error("Found {} errors", errors.len())]
+pub struct MultipleErrors {
+    #[related]
+    pub(crate) errors: Vec<Error>,
+}
+
+#[derive(Debug, ThisError, Diagnostic)]
+#[error("Code generation reported {count} warning(s), which are denied")]
+#[diagnostic(help(
+    "fix them, or allow them by removing `--deny-warnings` or the `deny-warnings` setting"
+))]
+pub struct DeniedWarningsError {
+    pub(crate) count: usize,
+}
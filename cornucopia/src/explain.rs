@@ -0,0 +1,51 @@
+use self::error::Error;
+
+/// Extended explanations of error and warning codes, like `cornucopia::E0007`.
+const EXPLANATIONS: &[(&str, &str)] = &[
+    ("E0001", include_str!("explanations/E0001.md")),
+    ("E0002", include_str!("explanations/E0002.md")),
+    ("E0003", include_str!("explanations/E0003.md")),
+    ("E0004", include_str!("explanations/E0004.md")),
+    ("E0005", include_str!("explanations/E0005.md")),
+    ("E0006", include_str!("explanations/E0006.md")),
+    ("E0007", include_str!("explanations/E0007.md")),
+    ("E0008", include_str!("explanations/E0008.md")),
+    ("E0009", include_str!("explanations/E0009.md")),
+    ("E0010", include_str!("explanations/E0010.md")),
+    ("E0011", include_str!("explanations/E0011.md")),
+    ("E0012", include_str!("explanations/E0012.md")),
+    ("E0013", include_str!("explanations/E0013.md")),
+    ("E0014", include_str!("explanations/E0014.md")),
+    ("E0015", include_str!("explanations/E0015.md")),
+    ("E0016", include_str!("explanations/E0016.md")),
+    ("E0017", include_str!("explanations/E0017.md")),
+    ("W0001", include_str!("explanations/W0001.md")),
+    ("W0002", include_str!("explanations/W0002.md")),
+    ("W0003", include_str!("explanations/W0003.md")),
+    ("W0004", include_str!("explanations/W0004.md")),
+    ("W0005", include_str!("explanations/W0005.md")),
+];
+
+/// Returns the extended explanation of `code`, with or without its `cornucopia::` prefix.
+pub(crate) fn explain(code: &str) -> Result<&'static str, Error> {
+    let short = code.strip_prefix("cornucopia::").unwrap_or(code);
+    EXPLANATIONS
+        .iter()
+        .find(|(known, _)| known.eq_ignore_ascii_case(short))
+        .map(|(_, explanation)| *explanation)
+        .ok_or_else(|| Error::UnknownCode(code.to_string()))
+}
+
+pub(crate) mod error {
+    use miette::Diagnostic;
+    use thiserror::Error as ThisError;
+
+    #[derive(Debug, ThisError, Diagnostic)]
+    pub enum Error {
+        #[error("No explanation for the code `{0}`")]
+        #[diagnostic(help(
+            "codes look like `E0007` for errors and `W0001` for warnings, as printed before each diagnostic"
+        ))]
+        UnknownCode(String),
+    }
+}
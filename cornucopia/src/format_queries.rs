@@ -0,0 +1,225 @@
+use std::{io::Write, ops::Range};
+
+use similar::TextDiff;
+
+use crate::{
+    error::Error as CornucopiaError,
+    parse_query_modules,
+    parser::{parse_annotation_at, Annotation, Attribute, Module, NullableIdent, QueryDataStruct},
+};
+
+use self::error::Error;
+
+/// Formats the query files located at `queries_path`. Annotations are written in a canonical
+/// form with sorted nullity lists, broken into one item per line when they are too long.
+/// Comments are unindented and runs of blank lines are collapsed.
+/// The SQL of the queries is deliberately left as written, only trailing whitespace is removed:
+/// reformatting it would need a full SQL parser, and could change the meaning of queries.
+///
+/// With `check`, files are left untouched too: the diff of each file that is not formatted
+/// is written to `diff_output`, and an error is returned. Failing to write the diff, for
+/// example on a closed pipe, is not an error of its own. Files are only formatted if they
+/// all parse.
+pub fn format_queries(
+    queries_path: &str,
+    check: bool,
+    diff_output: &mut impl Write,
+) -> Result<(), CornucopiaError> {
+    let modules = parse_query_modules(queries_path)?;
+    let mut unformatted = 0;
+    for module in modules {
+        let formatted = format_module(&module);
+        let (path, content) = (&module.info.path, &module.info.content);
+        if formatted == *content {
+            continue;
+        }
+        if check {
+            unformatted += 1;
+            let diff = TextDiff::from_lines(content.as_str(), formatted.as_str());
+            // The check fails anyway, whether the diff could be written or not
+            write!(
+                diff_output,
+                "{}",
+                diff.unified_diff()
+                    .header(path, &format!("{path} (formatted)"))
+            )
+            .ok();
+        } else {
+            std::fs::write(path, formatted).map_err(|err| Error::Write {
+                path: path.clone(),
+                err,
+            })?;
+        }
+    }
+    if unformatted > 0 {
+        Err(Error::Unformatted { count: unformatted }.into())
+    } else {
+        Ok(())
+    }
+}
+
+/// Formats a module line by line, copying the lines holding SQL as they are.
+fn format_module(module: &Module) -> String {
+    let content = &module.info.content;
+    // Spans count chars, not bytes
+    let byte_offset = |offset: usize| {
+        content
+            .char_indices()
+            .nth(offset)
+            .map_or(content.len(), |(i, _)| i)
+    };
+    let sql: Vec<Range<usize>> = module
+        .queries
+        .iter()
+        .map(|query| {
+            let span = query.sql_span;
+            byte_offset(span.offset())..byte_offset(span.offset() + span.len())
+        })
+        .collect();
+
+    let mut formatted = String::with_capacity(content.len());
+    let mut pending_blank = false;
+    let mut start = 0;
+    // End of the last annotation, which may span several lines
+    let mut annotation_end = 0;
+    for line in content.split_inclusive('\n') {
+        let end = start + line.len();
+        let sql = sql.iter().find(|sql| sql.start < end && start < sql.end);
+        let line_start = std::mem::replace(&mut start, end);
+        if line_start < annotation_end {
+            continue;
+        }
+        if line.trim().is_empty() && sql.is_none() {
+            pending_blank = !formatted.is_empty();
+            continue;
+        }
+        if std::mem::take(&mut pending_blank) {
+            formatted.push('\n');
+        }
+        match sql {
+            // The end of a query, followed by what remains of its line
+            Some(sql) if sql.end < end => {
+                let (query, rest) = line.split_at(sql.end - line_start);
+                formatted.push_str(query);
+                formatted.push_str(rest.trim_end());
+                formatted.push('\n');
+            }
+            Some(_) => formatted.push_str(line),
+            None => {
+                let indent = line.len() - line.trim_start().len();
+                match parse_annotation_at(&content[line_start + indent..]) {
+                    Some((annotation, len)) => {
+                        annotation_end = line_start + indent + len;
+                        formatted.push_str(&format_annotation(&annotation));
+                    }
+                    // Comments
+                    None => formatted.push_str(line.trim()),
+                }
+                formatted.push('\n');
+            }
+        }
+    }
+    if !formatted.ends_with('\n') {
+        formatted.push('\n');
+    }
+    formatted
+}
+
+/// Width above which the lists of an annotation are broken into one item per line
+const MAX_WIDTH: usize = 100;
+
+fn format_annotation(annotation: &Annotation) -> String {
+    let formatted = format_annotation_with(annotation, false);
+    if formatted.len() > MAX_WIDTH {
+        format_annotation_with(annotation, true)
+    } else {
+        formatted
+    }
+}
+
+fn format_annotation_with(annotation: &Annotation, broken: bool) -> String {
+    match annotation {
+        Annotation::Type(ty) => format!("--: {}{}", ty.name, format_fields(&ty.fields, broken)),
+        Annotation::Query {
+            name,
+            param,
+            row,
+            attributes,
+        } => {
+            let mut formatted = format!("--! {name}");
+            if !param.is_empty() {
+                formatted.push(' ');
+                formatted.push_str(&format_struct(param, broken));
+            }
+            if !row.is_empty() {
+                formatted.push_str(": ");
+                formatted.push_str(&format_struct(row, broken));
+            }
+            if !attributes.is_empty() {
+                formatted.push(' ');
+                formatted.push_str(&format_attributes(attributes, broken));
+            }
+            formatted
+        }
+    }
+}
+
+fn format_struct(data: &QueryDataStruct, broken: bool) -> String {
+    let name = data.name.as_ref().map_or("", |name| name.value.as_str());
+    let fields = data
+        .idents
+        .as_deref()
+        .map(|fields| format_fields(fields, broken))
+        .unwrap_or_default();
+    format!("{name}{fields}")
+}
+
+/// Formats a nullity list, sorted by name.
+fn format_fields(fields: &[NullableIdent], broken: bool) -> String {
+    let mut fields: Vec<_> = fields.iter().collect();
+    fields.sort_by(|a, b| a.name.value.cmp(&b.name.value));
+    let fields: Vec<_> = fields
+        .into_iter()
+        .map(|field| {
+            let nullable = if field.nullable { "?" } else { "" };
+            let inner_nullable = if field.inner_nullable { "[?]" } else { "" };
+            format!("{}{nullable}{inner_nullable}", field.name)
+        })
+        .collect();
+    format_list("(", &fields, ")", broken)
+}
+
+fn format_attributes(attributes: &[Attribute], broken: bool) -> String {
+    let attributes: Vec<_> = attributes
+        .iter()
+        .map(|attribute| match &attribute.value {
+            Some(value) => format!("{} = \"{value}\"", attribute.name),
+            None => attribute.name.value.clone(),
+        })
+        .collect();
+    format_list("#[", &attributes, "]", broken)
+}
+
+/// Formats a list on one line, or with one item per continuation line when `broken`.
+fn format_list(open: &str, items: &[String], close: &str, broken: bool) -> String {
+    if broken && !items.is_empty() {
+        let items: String = items.iter().map(|it| format!("--   {it},\n")).collect();
+        format!("{open}\n{items}-- {close}")
+    } else {
+        format!("{open}{}{close}", items.join(", "))
+    }
+}
+
+pub(crate) mod error {
+    use miette::Diagnostic;
+    use thiserror::Error as ThisError;
+
+    #[derive(Debug, ThisError, Diagnostic)]
+    pub enum Error {
+        #[error("Could not write formatted queries to `{path}`: ({err})")]
+        Write { path: String, err: std::io::Error },
+        #[error("{count} query file(s) are not formatted")]
+        #[diagnostic(help("format them by running the same command without `--check`"))]
+        Unformatted { count: usize },
+    }
+}
@@ -1,104 +1,418 @@
 mod cli;
 mod codegen;
+mod config;
+mod dump_schema;
 mod error;
+mod explain;
+mod format_queries;
 mod load_schema;
+mod lsp;
+mod message_format;
+mod metadata;
 mod parser;
 mod prepare_queries;
 mod read_queries;
 mod type_registrar;
 mod utils;
 mod validation;
+mod warning;
+mod watch;
 
 /// Helpers to establish connections to database instances.
 pub mod conn;
 /// High-level interfaces to work with Cornucopia's container manager.
 pub mod container;
+/// High-level interfaces to work with a throwaway server using local PostgreSQL binaries.
+pub mod local;
 
-use postgres::Client;
+use std::{
+    collections::BTreeMap,
+    io::Write,
+    path::{Path, PathBuf},
+};
 
-use codegen::generate as generate_internal;
-use error::WriteOutputError;
-use parser::parse_query_module;
+use postgres::{Client, Config, NoTls};
+
+use codegen::{generate as generate_internal, HEADER};
+use container::ContainerSettings;
+use error::{OutdatedOutputError, WriteOutputError};
+use metadata::{Metadata, StmtSource};
+use parser::{parse_query_module, Module};
 use prepare_queries::prepare;
 use read_queries::read_query_modules;
 
 #[doc(hidden)]
 pub use cli::run;
 
+pub use codegen::GeneratedCode;
+pub use dump_schema::dump_schema;
 pub use error::Error;
+pub use format_queries::format_queries;
 pub use load_schema::load_schema;
+pub use warning::Warning;
 
 /// Struct containing the settings for code generation.
-#[derive(Clone, Copy)]
+#[derive(Clone, Default)]
 pub struct CodegenSettings {
     pub is_async: bool,
     pub derive_ser: bool,
+    /// Derives and attributes added to the generated types.
+    pub derives: DeriveSettings,
+}
+
+/// Derives and attributes added to the generated types, on top of the ones they always have.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeriveSettings {
+    /// Added to every row struct and to the types generated for Postgres types.
+    /// Params structs borrow their fields, so they only get the ones set in `structs`.
+    pub global: TypeDerives,
+    /// Added to the types generated for Postgres types, by schema-qualified name like `public.mood`.
+    pub types: BTreeMap<String, TypeDerives>,
+    /// Added to the row and params structs, by struct name. A bare name like `Row` applies in
+    /// every query module, a name qualified by its module like `billing::Row` to a single one.
+    pub structs: BTreeMap<String, TypeDerives>,
+}
+
+/// Derives and attributes added to a generated type.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TypeDerives {
+    /// Derive macros, like `Hash` or `utoipa::ToSchema`.
+    pub derives: Vec<String>,
+    /// Attributes written as is, like `#[serde(rename_all = "camelCase")]`.
+    pub attributes: Vec<String>,
+}
+
+/// Database server started by Cornucopia to prepare queries in managed mode.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ManagedBackend {
+    /// A container started with the given settings.
+    Container(ContainerSettings),
+    /// A throwaway server using the `initdb` and `pg_ctl` binaries from your `PATH`.
+    Local,
+}
+
+impl Default for ManagedBackend {
+    fn default() -> Self {
+        Self::Container(ContainerSettings::default())
+    }
 }
 
 /// Generates Rust queries from PostgreSQL queries located at `queries_path`,
 /// using a live database managed by you. If some `destination` is given,
-/// the generated code will be written at that path. Code generation settings are
-/// set using the `settings` parameter.
+/// the generated code will be written at that path (see [`write_generated_code`]).
+/// Code generation settings are set using the `settings` parameter.
 pub fn generate_live(
     client: &mut Client,
     queries_path: &str,
     destination: Option<&str>,
     settings: CodegenSettings,
-) -> Result<String, Error> {
+) -> Result<GeneratedCode, Error> {
+    generate_live_with_metadata(client, queries_path, destination, None, settings)
+}
+
+/// Like [`generate_live`], but if some `metadata` path is given, the prepared queries metadata
+/// will also be written at that path, to be used later by [`generate_offline`].
+pub fn generate_live_with_metadata(
+    client: &mut Client,
+    queries_path: &str,
+    destination: Option<&str>,
+    metadata: Option<&str>,
+    settings: CodegenSettings,
+) -> Result<GeneratedCode, Error> {
     // Read
-    let modules = read_query_modules(queries_path)?
-        .into_iter()
-        .map(parse_query_module)
-        .collect::<Result<_, parser::error::Error>>()?;
+    let modules = parse_query_modules(queries_path)?;
     // Generate
-    let prepared_modules = prepare(client, modules)?;
-    let generated_code = generate_internal(prepared_modules, settings);
+    let mut source = StmtSource::live(client);
+    let prepared_modules = prepare(&mut source, modules).map_err(Error::from_many)?;
+    let generated_code =
+        generate_internal(prepared_modules, &settings).map_err(Error::from_many)?;
     // Write
     if let Some(d) = destination {
         write_generated_code(d, &generated_code)?;
     };
+    if let Some(m) = metadata {
+        source.metadata().write(m)?;
+    };
 
     Ok(generated_code)
 }
 
 /// Generates Rust queries from PostgreSQL queries located at `queries_path`, using
-/// a container managed by cornucopia. The database schema is created using `schema_files`.
+/// a database server managed by cornucopia. The database schema is created using `schema_files`.
 /// If some `destination` is given, the generated code will be written at that path.
 /// Code generation settings are set using the `settings` parameter.
 ///
-/// By default, the container manager is Docker, but Podman can be used by setting the
-/// `podman` parameter to `true`.
+/// The database server is started using `backend`, and is always cleaned up afterward.
 pub fn generate_managed(
     queries_path: &str,
     schema_files: Vec<String>,
     destination: Option<&str>,
-    podman: bool,
+    backend: ManagedBackend,
     settings: CodegenSettings,
-) -> Result<String, Error> {
+) -> Result<GeneratedCode, Error> {
+    generate_managed_with_metadata(
+        queries_path,
+        schema_files,
+        destination,
+        None,
+        backend,
+        settings,
+    )
+}
+
+/// Like [`generate_managed`], but if some `metadata` path is given, the prepared queries metadata
+/// will also be written at that path, to be used later by [`generate_offline`].
+pub fn generate_managed_with_metadata(
+    queries_path: &str,
+    schema_files: Vec<String>,
+    destination: Option<&str>,
+    metadata: Option<&str>,
+    backend: ManagedBackend,
+    settings: CodegenSettings,
+) -> Result<GeneratedCode, Error> {
     // Read
-    let modules = read_query_modules(queries_path)?
-        .into_iter()
-        .map(parse_query_module)
-        .collect::<Result<_, parser::error::Error>>()?;
-    container::setup(podman)?;
-    let mut client = conn::cornucopia_conn()?;
-    load_schema(&mut client, schema_files)?;
-    let prepared_modules = prepare(&mut client, modules)?;
-    let generated_code = generate_internal(prepared_modules, settings);
-    container::cleanup(podman)?;
+    let modules = parse_query_modules(queries_path)?;
+    let (generated_code, prepared_metadata) = with_managed_db(backend, |client| {
+        load_schema(client, schema_files)?;
+        let mut source = StmtSource::live(client);
+        let prepared_modules = prepare(&mut source, modules).map_err(Error::from_many)?;
+        Ok((
+            generate_internal(prepared_modules, &settings).map_err(Error::from_many)?,
+            source.metadata().clone(),
+        ))
+    })?;
 
     if let Some(destination) = destination {
         write_generated_code(destination, &generated_code)?;
     };
+    if let Some(metadata) = metadata {
+        prepared_metadata.write(metadata)?;
+    };
 
     Ok(generated_code)
 }
 
-fn write_generated_code(destination: &str, generated_code: &str) -> Result<(), Error> {
-    Ok(
-        std::fs::write(destination, generated_code).map_err(|err| WriteOutputError {
-            err,
+/// Starts a database server using `backend` and runs `f` against it.
+/// The server is cleaned up even if `f` fails.
+fn with_managed_db<T>(
+    backend: ManagedBackend,
+    f: impl FnOnce(&mut Client) -> Result<T, Error>,
+) -> Result<T, Error> {
+    with_managed_server(backend, |config| {
+        let mut client = config.connect(NoTls).map_err(conn::error::Error::from)?;
+        f(&mut client)
+    })
+}
+
+/// Starts a database server using `backend` and runs `f` with the connection settings
+/// of its database. The server is cleaned up even if `f` fails.
+fn with_managed_server<T>(
+    backend: ManagedBackend,
+    f: impl FnOnce(&Config) -> Result<T, Error>,
+) -> Result<T, Error> {
+    match backend {
+        ManagedBackend::Container(settings) => {
+            let container = container::setup(&settings)?;
+            let result = f(&container.config());
+            let cleanup = container.cleanup();
+            let value = result?;
+            cleanup?;
+            Ok(value)
+        }
+        ManagedBackend::Local => {
+            let server = local::setup()?;
+            let result = f(&server.config());
+            let cleanup = server.cleanup();
+            let value = result?;
+            cleanup?;
+            Ok(value)
+        }
+    }
+}
+
+/// Reads and parses the modules located at `queries_path`, reporting the errors of all modules together.
+fn parse_query_modules(queries_path: &str) -> Result<Vec<Module>, Error> {
+    let mut modules = Vec::new();
+    let mut errors = Vec::new();
+    for info in read_query_modules(queries_path)? {
+        match parse_query_module(info) {
+            Ok(module) => modules.push(module),
+            Err(module_errors) => errors.extend(module_errors),
+        }
+    }
+    if errors.is_empty() {
+        Ok(modules)
+    } else {
+        Err(Error::from_many(errors))
+    }
+}
+
+/// Generates Rust queries from PostgreSQL queries located at `queries_path`, without
+/// any database. Queries are prepared using the `metadata` file written by a previous
+/// [`generate_live_with_metadata`] or [`generate_managed_with_metadata`] run, so it must be regenerated when queries
+/// or the database schema change. If some `destination` is given, the generated code will
+/// be written at that path. Code generation settings are set using the `settings` parameter.
+pub fn generate_offline(
+    queries_path: &str,
+    metadata: &str,
+    destination: Option<&str>,
+    settings: CodegenSettings,
+) -> Result<GeneratedCode, Error> {
+    // Read
+    let modules = parse_query_modules(queries_path)?;
+    // Generate
+    let mut source = StmtSource::offline(Metadata::read(metadata)?);
+    let prepared_modules = prepare(&mut source, modules).map_err(Error::from_many)?;
+    let generated_code =
+        generate_internal(prepared_modules, &settings).map_err(Error::from_many)?;
+    // Write
+    if let Some(d) = destination {
+        write_generated_code(d, &generated_code)?;
+    };
+
+    Ok(generated_code)
+}
+
+/// Serves the language server protocol on `connection` until the editor shuts it down,
+/// preparing queries using a live database managed by you. This is what the `lsp` command
+/// runs on the standard input and output. Code generation settings are set using the
+/// `settings` parameter.
+///
+/// Hidden as it exposes the `lsp-server` connection type, it is only meant for tests.
+#[doc(hidden)]
+pub fn serve_lsp_live(
+    client: &mut Client,
+    connection: lsp_server::Connection,
+    settings: CodegenSettings,
+) -> Result<(), Error> {
+    Ok(lsp::serve_connection(
+        connection,
+        &mut StmtSource::live(client),
+        settings,
+    )?)
+}
+
+/// Writes `generated_code` at `destination`. If `destination` ends with a `/`, it is a directory,
+/// and one file is written per module, starting with `mod.rs`. Previously generated files in it
+/// that are no longer part of the output are removed.
+pub fn write_generated_code(
+    destination: &str,
+    generated_code: &GeneratedCode,
+) -> Result<(), Error> {
+    let write_err = |path: &Path| {
+        let file_path = path.to_string_lossy().into_owned();
+        move |err| WriteOutputError { err, file_path }
+    };
+    for path in stale_files(destination, generated_code) {
+        std::fs::remove_file(&path).map_err(write_err(&path))?;
+        // Also remove directories of modules that no longer exist, which fails if they are not empty
+        if let Some(parent) = path.parent() {
+            std::fs::remove_dir(parent).ok();
+        }
+    }
+    for (path, content) in output_files(destination, generated_code) {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent).map_err(write_err(parent))?;
+        }
+        std::fs::write(&path, content).map_err(write_err(&path))?;
+    }
+    Ok(())
+}
+
+/// Compares `generated_code` with the content of `destination` without modifying it.
+/// If they differ, a unified diff is written to `diff_output` and an error is returned.
+pub fn check_generated_code(
+    destination: &str,
+    generated_code: &GeneratedCode,
+    diff_output: &mut impl Write,
+) -> Result<(), Error> {
+    let stale = stale_files(destination, generated_code)
+        .into_iter()
+        .map(|path| (path, String::new()));
+    let mut up_to_date = true;
+    for (path, generated) in output_files(destination, generated_code)
+        .into_iter()
+        .chain(stale)
+    {
+        let current = std::fs::read_to_string(&path).unwrap_or_default();
+        if current != generated {
+            up_to_date = false;
+            let path = path.to_string_lossy();
+            let diff = similar::TextDiff::from_lines(current.as_str(), generated.as_str());
+            // The check fails anyway, whether the diff could be written or not
+            write!(
+                diff_output,
+                "{}",
+                diff.unified_diff()
+                    .header(&path, &format!("{path} (generated)"))
+            )
+            .ok();
+        }
+    }
+    if up_to_date {
+        Ok(())
+    } else {
+        Err(OutdatedOutputError {
             file_path: String::from(destination),
-        })?,
-    )
+        }
+        .into())
+    }
+}
+
+/// Whether `destination` is a directory receiving one file per module, which must be explicit,
+/// as generated files missing from the output are removed from it.
+fn is_dir_destination(destination: &str) -> bool {
+    destination.ends_with(std::path::is_separator)
+}
+
+/// Files to write at `destination`, with their content.
+fn output_files(destination: &str, generated_code: &GeneratedCode) -> Vec<(PathBuf, String)> {
+    if is_dir_destination(destination) {
+        generated_code
+            .to_files()
+            .into_iter()
+            .map(|(path, content)| (Path::new(destination).join(path), content))
+            .collect()
+    } else {
+        vec![(PathBuf::from(destination), generated_code.to_single_file())]
+    }
+}
+
+/// Previously generated files in the `destination` directory which are not part of `generated_code`.
+fn stale_files(destination: &str, generated_code: &GeneratedCode) -> Vec<PathBuf> {
+    fn walk(dir: &Path, found: &mut Vec<PathBuf>) {
+        for entry in std::fs::read_dir(dir).into_iter().flatten().flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                walk(&path, found);
+            } else if path
+                .extension()
+                .map(|extension| extension == "rs")
+                .unwrap_or_default()
+            {
+                found.push(path);
+            }
+        }
+    }
+
+    if !is_dir_destination(destination) {
+        return Vec::new();
+    }
+    let generated: Vec<_> = output_files(destination, generated_code)
+        .into_iter()
+        .map(|(path, _)| path)
+        .collect();
+    let mut existing = Vec::new();
+    walk(Path::new(destination), &mut existing);
+    existing.sort();
+    // Only remove files we generated, identified by their header
+    existing
+        .into_iter()
+        .filter(|path| !generated.contains(path))
+        .filter(|path| {
+            std::fs::read_to_string(path)
+                .map(|content| content.starts_with(HEADER))
+                .unwrap_or_default()
+        })
+        .collect()
 }
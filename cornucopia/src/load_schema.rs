@@ -1,46 +1,482 @@
+use std::{
+    io::Write,
+    ops::Range,
+    path::{Path, PathBuf},
+};
+
 use miette::NamedSource;
-use postgres::Client;
+use postgres::{Client, Config, NoTls};
 
-use crate::utils::db_err;
+use crate::container::{drop_database, unique_name, DATABASE_PREFIX};
 
 use self::error::Error;
 
 /// Loads PostgreSQL schemas into a database.
 ///
 /// Takes a list of file paths as parameter and loads them in their given order.
+/// A directory is read as a migrations directory, whose migrations are loaded in version order.
+///
+/// Files are read like psql scripts, so that `pg_dump` output can be loaded: `\i` and `\ir`
+/// include files relative to the including file, `COPY ... FROM stdin` data is loaded, and
+/// meta-commands like `\connect` or `\set` are skipped.
+///
+/// Like separate psql runs, each file starts with the default settings: a `SET search_path`
+/// in a file, like the one of `pg_dump` output, doesn't carry over to the next files, but does
+/// to the files it includes. Files relying on such settings must set them again.
 pub fn load_schema(client: &mut Client, paths: Vec<String>) -> Result<(), Error> {
     for path in paths {
-        let sql = std::fs::read_to_string(&path).map_err(|err| Error::Io {
-            path: path.clone(),
-            err,
-        })?;
-        client.batch_execute(&sql).map_err(|err| {
-            let msg = format!("{:#}", err);
-            let src = NamedSource::new(path, sql);
-            if let Some((position, msg, help)) = db_err(&err) {
-                Error::Postgres {
-                    msg,
-                    help,
-                    src,
-                    err_span: Some((position as usize..position as usize).into()),
-                }
-            } else {
-                Error::Postgres {
-                    msg,
-                    help: None,
-                    src,
-                    err_span: None,
+        if Path::new(&path).is_dir() {
+            for migration in migration_files(&path)? {
+                load_file(client, migration)?;
+            }
+        } else {
+            load_file(client, path)?;
+        }
+    }
+    Ok(())
+}
+
+fn load_file(client: &mut Client, path: String) -> Result<(), Error> {
+    load_script(client, Path::new(&path), &mut Vec::new())?;
+    client.batch_execute("RESET ALL").map_err(Error::Reset)
+}
+
+/// Loads a psql script, following its includes. `loading` holds the scripts being loaded,
+/// to detect include cycles.
+fn load_script(client: &mut Client, path: &Path, loading: &mut Vec<PathBuf>) -> Result<(), Error> {
+    let path_str = path.to_string_lossy().into_owned();
+    let script = std::fs::read_to_string(path).map_err(|err| Error::Io {
+        path: path_str.clone(),
+        err,
+    })?;
+    let src = || NamedSource::new(&path_str, script.clone());
+    loading.push(path.canonicalize().unwrap_or_else(|_| path.to_path_buf()));
+    for segment in split_script(&path_str, &script)? {
+        match segment {
+            Segment::Sql(range) => client
+                .batch_execute(&script[range.clone()])
+                .map_err(|err| Error::postgres(err, src(), range.start, None))?,
+            Segment::Copy { statement, data } => {
+                let copy_err = |err| Error::postgres(err, src(), statement.start, Some(&statement));
+                let mut writer = client
+                    .copy_in(&script[statement.clone()])
+                    .map_err(copy_err)?;
+                // A failed write is reported by `finish`
+                writer.write_all(script[data].as_bytes()).ok();
+                writer.finish().map_err(copy_err)?;
+            }
+            Segment::Include {
+                path: included,
+                span,
+            } => {
+                // Includes are relative to the including script
+                let included = path.parent().unwrap_or(Path::new("")).join(included);
+                let canonical = included.canonicalize().unwrap_or_else(|_| included.clone());
+                if loading.contains(&canonical) {
+                    return Err(Error::IncludeCycle {
+                        path: included.to_string_lossy().into_owned(),
+                        src: src(),
+                        span: span.into(),
+                    });
                 }
+                load_script(client, &included, loading)?;
             }
-        })?;
+        }
     }
+    loading.pop();
     Ok(())
 }
 
+/// A part of a psql script, as byte ranges of the script.
+enum Segment<'a> {
+    /// SQL statements, executed as is.
+    Sql(Range<usize>),
+    /// A `COPY ... FROM stdin` statement, and the data following it.
+    Copy {
+        statement: Range<usize>,
+        data: Range<usize>,
+    },
+    /// A `\i` or `\ir` meta-command.
+    Include { path: &'a str, span: Range<usize> },
+}
+
+/// Meta-commands that don't affect the schema, which are skipped.
+const IGNORED_COMMANDS: [&str; 13] = [
+    "c",
+    "connect",
+    "echo",
+    "encoding",
+    "pset",
+    "qecho",
+    "restrict",
+    "set",
+    "setenv",
+    "timing",
+    "unrestrict",
+    "unset",
+    "warn",
+];
+
+/// Splits a psql script into the SQL sent to the database and the constructs handled
+/// by psql itself: meta-commands and `COPY ... FROM stdin` data.
+fn split_script<'a>(path: &str, script: &'a str) -> Result<Vec<Segment<'a>>, Error> {
+    let src = || NamedSource::new(path, String::from(script));
+    let bytes = script.as_bytes();
+    let mut segments = Vec::new();
+    // Start of the SQL not yet in a segment, and of the current statement
+    let mut chunk_start = 0;
+    let mut statement_start = None;
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'-' if bytes[i..].starts_with(b"--") => {
+                i = line_end(script, i);
+                continue;
+            }
+            b'/' if bytes[i..].starts_with(b"/*") => {
+                i = block_comment_end(bytes, i);
+                continue;
+            }
+            b'\'' | b'"' | b'$' => {
+                statement_start.get_or_insert(i);
+                i = quoted_end(script, i).unwrap_or(i + 1);
+                continue;
+            }
+            b'\\' => {
+                let end = line_end(script, i);
+                push_sql(&mut segments, script, chunk_start..i);
+                let mut args = script[i + 1..end].split_whitespace();
+                match args.next().unwrap_or_default() {
+                    "i" | "include" | "ir" | "include_relative" => {
+                        let path = args.next().ok_or_else(|| Error::MissingInclude {
+                            src: src(),
+                            span: (i..end).into(),
+                        })?;
+                        segments.push(Segment::Include {
+                            path: path.trim_matches('\''),
+                            span: i..end,
+                        });
+                    }
+                    command if IGNORED_COMMANDS.contains(&command) => {}
+                    command => {
+                        return Err(Error::UnsupportedCommand {
+                            command: String::from(command),
+                            src: src(),
+                            span: (i..end).into(),
+                        })
+                    }
+                }
+                chunk_start = end;
+                statement_start = None;
+                i = end;
+                continue;
+            }
+            b';' => {
+                if let Some(start) = statement_start.take() {
+                    if is_copy_from_stdin(&script[start..i]) {
+                        let (data, end) =
+                            copy_data(script, i).ok_or_else(|| Error::UnterminatedCopy {
+                                src: src(),
+                                span: (start..i).into(),
+                            })?;
+                        push_sql(&mut segments, script, chunk_start..start);
+                        segments.push(Segment::Copy {
+                            statement: start..i,
+                            data,
+                        });
+                        chunk_start = end;
+                        i = end;
+                        continue;
+                    }
+                }
+            }
+            c if c.is_ascii_whitespace() => {}
+            _ => {
+                statement_start.get_or_insert(i);
+            }
+        }
+        i += 1;
+    }
+    push_sql(&mut segments, script, chunk_start..script.len());
+    Ok(segments)
+}
+
+fn push_sql(segments: &mut Vec<Segment>, script: &str, range: Range<usize>) {
+    if !script[range.clone()].trim().is_empty() {
+        segments.push(Segment::Sql(range));
+    }
+}
+
+/// Position of the end of the line containing `i`.
+fn line_end(script: &str, i: usize) -> usize {
+    script[i..].find('\n').map_or(script.len(), |end| i + end)
+}
+
+/// Position after the block comment starting at `start`. Block comments can be nested.
+fn block_comment_end(bytes: &[u8], start: usize) -> usize {
+    let mut depth = 0;
+    let mut i = start;
+    while i < bytes.len() {
+        if bytes[i..].starts_with(b"/*") {
+            depth += 1;
+            i += 2;
+        } else if bytes[i..].starts_with(b"*/") {
+            depth -= 1;
+            i += 2;
+            if depth == 0 {
+                return i;
+            }
+        } else {
+            i += 1;
+        }
+    }
+    bytes.len()
+}
+
+/// Position after the string, quoted identifier or dollar-quoted string starting at `start`,
+/// or `None` if the `$` at `start` doesn't start a dollar quote.
+fn quoted_end(script: &str, start: usize) -> Option<usize> {
+    let bytes = script.as_bytes();
+    let is_ident = |b: u8| b.is_ascii_alphanumeric() || b == b'_' || !b.is_ascii();
+    match bytes[start] {
+        b'\'' => {
+            // Backslashes escape characters in `E'...'` strings
+            let escapes = start > 0
+                && bytes[start - 1].eq_ignore_ascii_case(&b'e')
+                && (start < 2 || !is_ident(bytes[start - 2]));
+            let mut i = start + 1;
+            while i < bytes.len() {
+                match bytes[i] {
+                    b'\\' if escapes => i += 1,
+                    b'\'' => return Some(i + 1),
+                    _ => {}
+                }
+                i += 1;
+            }
+            Some(bytes.len())
+        }
+        b'"' => Some(
+            script[start + 1..]
+                .find('"')
+                .map_or(bytes.len(), |end| start + end + 2),
+        ),
+        b'$' => {
+            if start > 0 && is_ident(bytes[start - 1]) {
+                return None;
+            }
+            let tag_len = bytes[start + 1..]
+                .iter()
+                .position(|b| !is_ident(*b))
+                .filter(|len| bytes[start + 1 + len] == b'$')?;
+            // Tags can't start with a digit, like in `$1`
+            if bytes[start + 1].is_ascii_digit() {
+                return None;
+            }
+            let tag = &script[start..start + tag_len + 2];
+            let content = start + tag.len();
+            Some(
+                script[content..]
+                    .find(tag)
+                    .map_or(bytes.len(), |end| content + end + tag.len()),
+            )
+        }
+        _ => None,
+    }
+}
+
+fn is_copy_from_stdin(statement: &str) -> bool {
+    let words: Vec<_> = statement
+        .split_whitespace()
+        .map(str::to_lowercase)
+        .collect();
+    words.first().map(|word| word == "copy").unwrap_or(false)
+        && words
+            .windows(2)
+            .any(|pair| pair[0] == "from" && pair[1] == "stdin")
+}
+
+/// Finds the data of the `COPY` statement ending at `semicolon`, which starts on the next line
+/// and ends with a `\.` line. Returns the data and the position after it.
+fn copy_data(script: &str, semicolon: usize) -> Option<(Range<usize>, usize)> {
+    let data_start = (line_end(script, semicolon) + 1).min(script.len());
+    let mut line_start = data_start;
+    while line_start < script.len() {
+        let end = line_end(script, line_start);
+        if script[line_start..end].trim_end_matches('\r') == "\\." {
+            return Some((data_start..line_start, end));
+        }
+        line_start = end + 1;
+    }
+    None
+}
+
+/// Lists the up migrations of a migrations directory, sorted by version. Supported layouts are
+/// `V<version>__<name>.sql` (refinery, flyway), `<version>_<name>.sql` and `<version>_<name>.up.sql`
+/// (sqlx, golang-migrate) and `<version>_<name>/up.sql` (diesel). Down migrations are skipped.
+/// Flyway repeatable migrations, `R__<name>.sql`, come after the versioned ones, sorted by name.
+fn migration_files(dir: &str) -> Result<Vec<String>, Error> {
+    let io_err = |err| Error::Io {
+        path: String::from(dir),
+        err,
+    };
+    let mut migrations = Vec::new();
+    let mut repeatables = Vec::new();
+    for entry in std::fs::read_dir(dir).map_err(io_err)? {
+        let path = entry.map_err(io_err)?.path();
+        let file_name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let (stem, file) = if path.is_dir() {
+            let up = path.join("up.sql");
+            if !up.is_file() {
+                continue;
+            }
+            (file_name.as_str(), up)
+        } else if file_name.ends_with(".down.sql") || is_undo(&file_name) {
+            continue;
+        } else if let Some(stem) = file_name
+            .strip_suffix(".up.sql")
+            .or_else(|| file_name.strip_suffix(".sql"))
+        {
+            (stem, path.clone())
+        } else {
+            continue;
+        };
+        let file = file.to_string_lossy().into_owned();
+        if let Some(name) = stem.strip_prefix("R__") {
+            repeatables.push((String::from(name), file));
+            continue;
+        }
+        match migration_version(stem) {
+            Some(version) => migrations.push((version, file)),
+            None => return Err(Error::InvalidMigration { path: file }),
+        }
+    }
+    migrations.sort();
+    repeatables.sort();
+    Ok(migrations
+        .into_iter()
+        .map(|(_, file)| file)
+        .chain(repeatables.into_iter().map(|(_, file)| file))
+        .collect())
+}
+
+/// Flyway undo migrations, `U<version>__<name>.sql`.
+fn is_undo(file_name: &str) -> bool {
+    file_name
+        .strip_prefix('U')
+        .map(|rest| rest.starts_with(|c: char| c.is_ascii_digit()) && rest.contains("__"))
+        .unwrap_or(false)
+}
+
+/// Parses the version of a migration from its name, as numeric components compared in order,
+/// so that `V2__a` comes before `V10__b` and `2022-01-01-000000_a` before `2022-01-02-000000_b`.
+fn migration_version(stem: &str) -> Option<Vec<u64>> {
+    let version = match stem.strip_prefix('V') {
+        Some(versioned) => versioned.split_once("__")?.0,
+        None => stem.split_once('_')?.0,
+    };
+    let is_version = version.starts_with(|c: char| c.is_ascii_digit())
+        && version
+            .chars()
+            .all(|c| c.is_ascii_digit() || matches!(c, '.' | '_' | '-'));
+    if !is_version {
+        return None;
+    }
+    version
+        .split(|c: char| !c.is_ascii_digit())
+        .filter(|part| !part.is_empty())
+        .map(|part| part.parse().ok())
+        .collect()
+}
+
+/// Loads schema files again and again on the same server, as in watch mode. Each load happens
+/// in a fresh database, and as roles are shared by all the databases of a server, the roles
+/// created by a load are dropped before the next one.
+pub(crate) struct SchemaReloader {
+    config: Config,
+    /// Connection to the database of `config`, from which fresh databases are created
+    admin: Client,
+    /// Database and roles created by the last load
+    created: Option<(String, Vec<String>)>,
+}
+
+impl SchemaReloader {
+    pub(crate) fn new(config: &Config) -> Result<Self, Error> {
+        Ok(Self {
+            config: config.clone(),
+            admin: config.connect(NoTls).map_err(Error::Reset)?,
+            created: None,
+        })
+    }
+
+    /// Drops what the last load created, then loads `paths` into a fresh database.
+    /// Connections to the previous database are terminated.
+    pub(crate) fn reload(&mut self, paths: Vec<String>) -> Result<Client, Error> {
+        self.drop_created()?;
+        let dbname = unique_name(DATABASE_PREFIX);
+        let before = self.roles()?;
+        self.admin
+            .batch_execute(&format!("CREATE DATABASE \"{dbname}\""))
+            .map_err(Error::Reset)?;
+        self.created = Some((dbname.clone(), Vec::new()));
+        let mut client = self
+            .config
+            .clone()
+            .dbname(&dbname)
+            .connect(NoTls)
+            .map_err(Error::Reset)?;
+        let loaded = load_schema(&mut client, paths);
+        // Some roles may have been created even if loading failed
+        let roles = self
+            .roles()?
+            .into_iter()
+            .filter(|role| !before.contains(role))
+            .collect();
+        self.created = Some((dbname, roles));
+        loaded.map(|()| client)
+    }
+
+    /// Drops what the last load created.
+    pub(crate) fn cleanup(mut self) -> Result<(), Error> {
+        self.drop_created()
+    }
+
+    fn drop_created(&mut self) -> Result<(), Error> {
+        if let Some((dbname, roles)) = self.created.take() {
+            drop_database(&mut self.admin, &dbname).map_err(Error::Reset)?;
+            for role in roles {
+                self.admin
+                    .batch_execute(&format!(
+                        "DROP ROLE IF EXISTS \"{}\"",
+                        role.replace('"', "\"\"")
+                    ))
+                    .map_err(Error::Reset)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn roles(&mut self) -> Result<Vec<String>, Error> {
+        Ok(self
+            .admin
+            .query("SELECT rolname FROM pg_roles", &[])
+            .map_err(Error::Reset)?
+            .iter()
+            .map(|row| row.get(0))
+            .collect())
+    }
+}
+
 pub(crate) mod error {
+    use std::ops::Range;
+
     use miette::{Diagnostic, NamedSource, SourceSpan};
     use thiserror::Error as ThisError;
 
+    use crate::utils::db_err;
+
     #[derive(Debug, ThisError, Diagnostic)]
     pub enum Error {
         #[error("Could not read schema `{path}`: ({err})")]
@@ -55,5 +491,81 @@ pub(crate) mod error {
             #[label("error occurs near this location")]
             err_span: Option<SourceSpan>,
         },
+        #[error("Could not execute schema: unsupported psql meta-command `\\{command}`")]
+        #[diagnostic(help(
+            "only `\\i` and `\\ir` are executed, and commands like `\\connect` or `\\set` are skipped"
+        ))]
+        UnsupportedCommand {
+            command: String,
+            #[source_code]
+            src: NamedSource,
+            #[label("unsupported meta-command")]
+            span: SourceSpan,
+        },
+        #[error("Could not execute schema: missing file to include")]
+        MissingInclude {
+            #[source_code]
+            src: NamedSource,
+            #[label("include without file")]
+            span: SourceSpan,
+        },
+        #[error("Could not execute schema: `{path}` includes itself")]
+        IncludeCycle {
+            path: String,
+            #[source_code]
+            src: NamedSource,
+            #[label("included here")]
+            span: SourceSpan,
+        },
+        #[error("Could not execute schema: COPY data doesn't end with a `\\.` line")]
+        UnterminatedCopy {
+            #[source_code]
+            src: NamedSource,
+            #[label("COPY statement")]
+            span: SourceSpan,
+        },
+        #[error("Could not read migration `{path}`: its name doesn't start with a version")]
+        #[diagnostic(help(
+            "name migrations like `V1__name.sql`, `R__name.sql`, `<version>_name.sql`, `<version>_name.up.sql` or `<version>_name/up.sql`"
+        ))]
+        InvalidMigration { path: String },
+        #[error("Could not reset the database: ({0:#})")]
+        Reset(postgres::Error),
+    }
+
+    impl Error {
+        /// Locates a database error in its script, `offset` being the position of the executed SQL.
+        /// Errors without position point at `statement` if any.
+        pub(crate) fn postgres(
+            err: postgres::Error,
+            src: NamedSource,
+            offset: usize,
+            statement: Option<&Range<usize>>,
+        ) -> Self {
+            if let Some((position, msg, help)) = db_err(&err) {
+                let position = offset + position as usize;
+                Error::Postgres {
+                    msg,
+                    help,
+                    src,
+                    err_span: Some((position..position).into()),
+                }
+            } else if let Some(db_err) = err.as_db_error() {
+                // Errors in COPY data tell the faulty line in their context
+                Error::Postgres {
+                    msg: db_err.message().to_string(),
+                    help: db_err.hint().or_else(|| db_err.where_()).map(String::from),
+                    src,
+                    err_span: statement.map(|range| range.clone().into()),
+                }
+            } else {
+                Error::Postgres {
+                    msg: format!("{:#}", err),
+                    help: None,
+                    src,
+                    err_span: statement.map(|range| range.clone().into()),
+                }
+            }
+        }
     }
 }
@@ -0,0 +1,202 @@
+use std::{
+    io::Write,
+    net::TcpListener,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
+
+use postgres::{Client, Config, NoTls};
+use tempfile::TempDir;
+
+use self::error::Error;
+
+/// A throwaway PostgreSQL server started from the `initdb` and `pg_ctl` binaries
+/// found in your `PATH`. Its data directory lives in a temporary directory.
+/// The server is stopped when dropped, but only [`LocalServer::cleanup`] reports errors.
+pub struct LocalServer {
+    /// Taken by `cleanup`, so that the server isn't stopped again when dropped
+    dir: Option<TempDir>,
+    port: u16,
+}
+
+/// Attempts at starting the server, as another process may take its port between the time
+/// it is found free and the time the server listens on it.
+const START_ATTEMPTS: usize = 3;
+
+/// Initializes a database cluster in a temporary directory and starts it on a free port.
+/// If the server doesn't start, it is stopped before returning the error.
+pub fn setup() -> Result<LocalServer, Error> {
+    let dir = tempfile::Builder::new()
+        .prefix("cornucopia_postgres")
+        .tempdir()?;
+    let mut server = LocalServer {
+        port: 0,
+        dir: Some(dir),
+    };
+    cmd(
+        Command::new("initdb")
+            .args(["--username", "postgres", "--auth", "trust", "--no-sync"])
+            .arg("--pgdata")
+            .arg(server.data_dir()),
+        "initialize the database cluster",
+    )?;
+    let mut attempt = 1;
+    loop {
+        server.port = free_port()?;
+        server.configure()?;
+        match server.start() {
+            Ok(()) => return Ok(server),
+            Err(_) if attempt < START_ATTEMPTS && is_port_taken(server.port) => {
+                server.stop().ok();
+                attempt += 1;
+            }
+            Err(err) => {
+                // The server may be running even if it didn't become ready in time
+                server.cleanup().ok();
+                return Err(err);
+            }
+        }
+    }
+}
+
+impl LocalServer {
+    /// Create a non-TLS connection to this server.
+    pub fn conn(&self) -> Result<Client, crate::conn::error::Error> {
+        Ok(self.config().connect(NoTls)?)
+    }
+
+    /// Connection settings to this server's database.
+    pub(crate) fn config(&self) -> Config {
+        let mut config = Config::new();
+        config
+            .user("postgres")
+            .host("127.0.0.1")
+            .port(self.port)
+            .dbname("postgres");
+        config
+    }
+
+    /// Stops the server and removes its data directory.
+    pub fn cleanup(mut self) -> Result<(), Error> {
+        self.stop()?;
+        if let Some(dir) = self.dir.take() {
+            dir.close()?;
+        }
+        Ok(())
+    }
+
+    fn start(&self) -> Result<(), Error> {
+        cmd(
+            Command::new("pg_ctl")
+                .args(["start", "--wait", "--pgdata"])
+                .arg(self.data_dir())
+                .arg("--log")
+                .arg(self.dir().join("postgres.log")),
+            "start the server",
+        )
+    }
+
+    fn stop(&self) -> Result<(), Error> {
+        cmd(
+            Command::new("pg_ctl")
+                .args(["stop", "--wait", "--mode", "immediate", "--pgdata"])
+                .arg(self.data_dir()),
+            "stop the server",
+        )
+    }
+
+    fn dir(&self) -> &Path {
+        self.dir
+            .as_ref()
+            .expect("the directory is only taken on cleanup")
+            .path()
+    }
+
+    fn data_dir(&self) -> PathBuf {
+        self.dir().join("data")
+    }
+
+    /// Listen on our port only, and skip durability as the data is thrown away anyway.
+    fn configure(&self) -> Result<(), Error> {
+        // Settings are appended to the configuration file to avoid quoting paths on the command
+        // line. The last occurrence of a setting wins, so that the port can be changed again.
+        let socket_dir = self.dir().to_string_lossy().replace('\'', "''");
+        let mut conf = std::fs::OpenOptions::new()
+            .append(true)
+            .open(self.data_dir().join("postgresql.conf"))?;
+        writeln!(
+            conf,
+            "port = {}\nlisten_addresses = '127.0.0.1'\nunix_socket_directories = '{socket_dir}'\nfsync = off",
+            self.port
+        )?;
+        Ok(())
+    }
+}
+
+impl Drop for LocalServer {
+    fn drop(&mut self) {
+        // Best effort, the temporary directory is removed afterward anyway
+        if self.dir.is_some() {
+            self.stop().ok();
+        }
+    }
+}
+
+/// Asks the OS for a currently unused port.
+fn free_port() -> Result<u16, Error> {
+    Ok(TcpListener::bind(("127.0.0.1", 0))?.local_addr()?.port())
+}
+
+/// Whether another process listens on `port`.
+fn is_port_taken(port: u16) -> bool {
+    TcpListener::bind(("127.0.0.1", port)).is_err()
+}
+
+fn cmd(command: &mut Command, action: &'static str) -> Result<(), Error> {
+    let program = command.get_program().to_string_lossy().to_string();
+    let output = command
+        .stderr(Stdio::piped())
+        .stdout(Stdio::null())
+        .output()
+        .map_err(|err| Error::new(format!("`{program}` couldn't {action}: {err}")))?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        let err = String::from_utf8_lossy(&output.stderr);
+        Err(Error::new(format!("`{program}` couldn't {action}: {err}")))
+    }
+}
+
+pub(crate) mod error {
+    use std::fmt::Debug;
+
+    use miette::Diagnostic;
+    use thiserror::Error as ThisError;
+
+    #[derive(Debug, ThisError, Diagnostic)]
+    #[error("{msg}")]
+    pub struct Error {
+        msg: String,
+        #[help]
+        pub help: Option<String>,
+    }
+
+    impl Error {
+        pub fn new(msg: String) -> Self {
+            Error {
+                msg,
+                help: Some(String::from("Make sure that PostgreSQL's `initdb` and `pg_ctl` binaries are in your `PATH`. They cannot be run as root.")),
+            }
+        }
+    }
+
+    impl From<std::io::Error> for Error {
+        fn from(e: std::io::Error) -> Self {
+            Self {
+                msg: format!("{e:#}"),
+                help: None,
+            }
+        }
+    }
+}
@@ -0,0 +1,603 @@
+use std::{collections::HashMap, path::Path};
+
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, Response};
+use lsp_types::{
+    notification::{
+        DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument, DidSaveTextDocument,
+        Notification as _, PublishDiagnostics,
+    },
+    request::{Completion, GotoDefinition, HoverRequest, Request as _},
+    CompletionItem, CompletionItemKind, CompletionOptions, CompletionParams, CompletionResponse,
+    Diagnostic, DiagnosticRelatedInformation, DiagnosticSeverity, GotoDefinitionParams,
+    GotoDefinitionResponse, Hover, HoverContents, HoverParams, HoverProviderCapability, Location,
+    MarkupContent, MarkupKind, NumberOrString, OneOf, Position, PublishDiagnosticsParams, Range,
+    ServerCapabilities, TextDocumentPositionParams, TextDocumentSyncCapability,
+    TextDocumentSyncKind, TextDocumentSyncOptions, TextDocumentSyncSaveOptions, Url,
+};
+use miette::{Severity, SourceSpan};
+
+use crate::{
+    codegen::idx_char,
+    metadata::StmtSource,
+    parser::{parse_annotation_at, parse_query_module, parse_sql_at, Annotation},
+    prepare_queries::{prepare, PreparedItem, PreparedModule},
+    read_queries::ModuleInfo,
+    utils::unescape_keyword,
+    CodegenSettings,
+};
+
+use self::error::Error;
+
+/// Serves the language server protocol on the standard input and output, until the editor
+/// shuts it down. Queries are prepared using `source`.
+///
+/// Open query files are checked when they are opened and saved. Hovering an annotation shows
+/// the Rust types generated for it, row references lead to their `--:` declaration, and
+/// nullity lists complete the names of the columns and parameters of their query.
+pub(crate) fn serve(source: &mut StmtSource, settings: CodegenSettings) -> Result<(), Error> {
+    let (connection, io_threads) = Connection::stdio();
+    serve_connection(connection, source, settings)?;
+    io_threads.join()?;
+    Ok(())
+}
+
+/// Serves the language server protocol on `connection`, like [`serve`].
+pub(crate) fn serve_connection(
+    connection: Connection,
+    source: &mut StmtSource,
+    settings: CodegenSettings,
+) -> Result<(), Error> {
+    let capabilities = ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Options(
+            TextDocumentSyncOptions {
+                open_close: Some(true),
+                change: Some(TextDocumentSyncKind::FULL),
+                save: Some(TextDocumentSyncSaveOptions::Supported(true)),
+                ..Default::default()
+            },
+        )),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        definition_provider: Some(OneOf::Left(true)),
+        completion_provider: Some(CompletionOptions {
+            trigger_characters: Some(vec![String::from("("), String::from(",")]),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    connection
+        .initialize(serde_json::to_value(capabilities).expect("capabilities are valid JSON"))?;
+    Server {
+        connection,
+        source,
+        settings,
+        documents: HashMap::new(),
+        analyses: HashMap::new(),
+    }
+    .run()
+}
+
+struct Server<'a, 'b> {
+    connection: Connection,
+    source: &'a mut StmtSource<'b>,
+    settings: CodegenSettings,
+    /// Current content of the open files, saved or not
+    documents: HashMap<Url, String>,
+    /// Last successful preparation of the open files
+    analyses: HashMap<Url, PreparedModule>,
+}
+
+impl Server<'_, '_> {
+    fn run(mut self) -> Result<(), Error> {
+        while let Ok(message) = self.connection.receiver.recv() {
+            match message {
+                Message::Request(request) => {
+                    if self.connection.handle_shutdown(&request)? {
+                        return Ok(());
+                    }
+                    let response = match request.method.as_str() {
+                        HoverRequest::METHOD => self.respond::<HoverRequest>(request, Self::hover),
+                        GotoDefinition::METHOD => {
+                            self.respond::<GotoDefinition>(request, Self::definition)
+                        }
+                        Completion::METHOD => self.respond::<Completion>(request, Self::completion),
+                        _ => Response::new_err(
+                            request.id,
+                            ErrorCode::MethodNotFound as i32,
+                            format!("unsupported request `{}`", request.method),
+                        ),
+                    };
+                    self.send(response)?;
+                }
+                Message::Notification(notification) => self.notify(notification)?,
+                Message::Response(_) => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn send(&self, message: impl Into<Message>) -> Result<(), Error> {
+        self.connection
+            .sender
+            .send(message.into())
+            .map_err(|_| Error::Disconnected)
+    }
+
+    fn respond<R: lsp_types::request::Request>(
+        &mut self,
+        request: Request,
+        f: fn(&mut Self, R::Params) -> R::Result,
+    ) -> Response {
+        match serde_json::from_value(request.params) {
+            Ok(params) => Response::new_ok(request.id, f(self, params)),
+            Err(err) => {
+                Response::new_err(request.id, ErrorCode::InvalidParams as i32, err.to_string())
+            }
+        }
+    }
+
+    fn notify(&mut self, notification: Notification) -> Result<(), Error> {
+        fn params<N: lsp_types::notification::Notification>(
+            notification: Notification,
+        ) -> Option<N::Params> {
+            serde_json::from_value(notification.params).ok()
+        }
+
+        match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                if let Some(params) = params::<DidOpenTextDocument>(notification) {
+                    let uri = params.text_document.uri;
+                    self.documents
+                        .insert(uri.clone(), params.text_document.text);
+                    self.check(uri)?;
+                }
+            }
+            DidChangeTextDocument::METHOD => {
+                if let Some(mut params) = params::<DidChangeTextDocument>(notification) {
+                    // Changes hold the full content of the file
+                    if let Some(change) = params.content_changes.pop() {
+                        self.documents.insert(params.text_document.uri, change.text);
+                    }
+                }
+            }
+            DidSaveTextDocument::METHOD => {
+                if let Some(params) = params::<DidSaveTextDocument>(notification) {
+                    self.check(params.text_document.uri)?;
+                }
+            }
+            DidCloseTextDocument::METHOD => {
+                if let Some(params) = params::<DidCloseTextDocument>(notification) {
+                    let uri = params.text_document.uri;
+                    self.documents.remove(&uri);
+                    self.analyses.remove(&uri);
+                    self.publish(uri, Vec::new())?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Parses and prepares a file, and publishes its errors and warnings.
+    fn check(&mut self, uri: Url) -> Result<(), Error> {
+        let text = match self.documents.get(&uri) {
+            Some(text) => text.clone(),
+            None => return Ok(()),
+        };
+        let mut diagnostics = Vec::new();
+        match parse_query_module(module_info(&uri, &text)) {
+            Ok(module) => match prepare(self.source, vec![module]) {
+                Ok(mut preparation) => {
+                    let module = preparation.modules.remove(0);
+                    for warning in &module.warnings {
+                        push_diagnostics(warning, &uri, &text, &mut diagnostics);
+                    }
+                    self.analyses.insert(uri.clone(), module);
+                }
+                Err(errors) => {
+                    for err in &errors {
+                        push_diagnostics(err, &uri, &text, &mut diagnostics);
+                    }
+                }
+            },
+            Err(errors) => {
+                for err in &errors {
+                    push_diagnostics(err, &uri, &text, &mut diagnostics);
+                }
+            }
+        }
+        self.publish(uri, diagnostics)
+    }
+
+    fn publish(&self, uri: Url, diagnostics: Vec<Diagnostic>) -> Result<(), Error> {
+        let params = PublishDiagnosticsParams {
+            uri,
+            diagnostics,
+            version: None,
+        };
+        self.send(Notification::new(
+            PublishDiagnostics::METHOD.to_string(),
+            params,
+        ))
+    }
+
+    /// Shows the generated parameters and row of a query, or the fields of a named type.
+    fn hover(&mut self, params: HoverParams) -> Option<Hover> {
+        let TextDocumentPositionParams {
+            text_document,
+            position,
+        } = params.text_document_position_params;
+        let text = self.documents.get(&text_document.uri)?;
+        let module = self.analyses.get(&text_document.uri)?;
+        let is_async = self.settings.is_async;
+        let mut sections = Vec::new();
+        match annotation_at(text, position.line)?.0 {
+            Annotation::Query { name, .. } => {
+                let query = module.queries.values().find(|it| it.name == name.value)?;
+                if let Some((idx, _)) = &query.param {
+                    let (_, params) = module.params.get_index(*idx)?;
+                    sections.push(("Parameters", render_params(params, is_async)));
+                }
+                if let Some((idx, _)) = &query.row {
+                    let (_, row) = module.rows.get_index(*idx)?;
+                    sections.push(("Row", render_row(row)));
+                }
+            }
+            Annotation::Type(ty) => {
+                if let Some(params) = module.params.values().find(|it| it.name == ty.name) {
+                    sections.push(("Parameters", render_params(params, is_async)));
+                }
+                if let Some(row) = module.rows.values().find(|it| it.name == ty.name) {
+                    sections.push(("Row", render_row(row)));
+                }
+            }
+        }
+        if sections.is_empty() {
+            return None;
+        }
+        let value = sections
+            .into_iter()
+            .map(|(title, code)| format!("**{title}**\n```rust\n{code}\n```"))
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        Some(Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value,
+            }),
+            range: None,
+        })
+    }
+
+    /// Goes from a named row or parameters type of a query to its `--:` declaration.
+    fn definition(&mut self, params: GotoDefinitionParams) -> Option<GotoDefinitionResponse> {
+        let TextDocumentPositionParams {
+            text_document,
+            position,
+        } = params.text_document_position_params;
+        let text = self.documents.get(&text_document.uri)?;
+        let (annotation, start) = annotation_at(text, position.line)?;
+        // Spans count chars, not bytes
+        let cursor = text.get(start..offset(text, position))?.chars().count();
+        let name = match annotation {
+            Annotation::Query { param, row, .. } => [param, row]
+                .into_iter()
+                .filter(|it| it.idents.is_none())
+                .filter_map(|it| it.name)
+                .find(|name| contains(&name.span, cursor))?,
+            Annotation::Type(_) => return None,
+        };
+
+        let mut line_start = 0;
+        for line in text.split_inclusive('\n') {
+            if let Some((Annotation::Type(ty), _)) = line
+                .starts_with("--:")
+                .then(|| parse_annotation_at(&text[line_start..]))
+                .flatten()
+            {
+                if ty.name == name {
+                    let span = char_span(&text[line_start..], &ty.name.span);
+                    let start = line_start + span.offset();
+                    return Some(GotoDefinitionResponse::Scalar(Location {
+                        uri: text_document.uri,
+                        range: range(text, &(start..start + span.len()).into()),
+                    }));
+                }
+            }
+            line_start += line.len();
+        }
+        None
+    }
+
+    /// Completes the names of the columns and parameters inside nullity lists.
+    fn completion(&mut self, params: CompletionParams) -> Option<CompletionResponse> {
+        let TextDocumentPositionParams {
+            text_document,
+            position,
+        } = params.text_document_position;
+        let text = self.documents.get(&text_document.uri)?.clone();
+        let (line, line_start) = line_at(&text, position.line);
+        let before = &line[..offset(&text, position) - line_start];
+        // Only inside the last opened list
+        let open = before.rfind('(')?;
+        if before[open..].contains(')') {
+            return None;
+        }
+        let list = line[open + 1..].split(')').next().unwrap_or_default();
+        let listed: Vec<_> = list
+            .split(',')
+            .map(|it| it.trim().trim_end_matches(['?', '[', ']']))
+            .collect();
+
+        let candidates: Vec<(String, Option<String>)> = if before.starts_with("--!") {
+            // The query follows its annotation
+            let sql = &text[(line_start + line.len() + 1).min(text.len())..];
+            let (sql_str, bind_params) = parse_sql_at(sql)?;
+            let stmt = self.source.prepare(&sql_str).ok().flatten();
+            if before[..open].contains(':') {
+                stmt?
+                    .columns
+                    .iter()
+                    .map(|col| (col.name().to_string(), Some(col.type_().to_string())))
+                    .collect()
+            } else {
+                let types = stmt.map(|stmt| stmt.params).unwrap_or_default();
+                bind_params
+                    .iter()
+                    .enumerate()
+                    .map(|(i, param)| (param.value.clone(), types.get(i).map(|ty| ty.to_string())))
+                    .collect()
+            }
+        } else if before.starts_with("--:") {
+            let name = before[3..open].trim();
+            let module = self.analyses.get(&text_document.uri)?;
+            module
+                .params
+                .values()
+                .chain(module.rows.values())
+                .filter(|it| it.name.value == name)
+                .flat_map(|it| &it.fields)
+                .map(|field| {
+                    let pg_ty = field.ty.pg_ty().to_string();
+                    (unescape_keyword(&field.name).to_string(), Some(pg_ty))
+                })
+                .collect()
+        } else {
+            return None;
+        };
+
+        // The name being typed is still proposed
+        let typed = before[open + 1..]
+            .rsplit(',')
+            .next()
+            .unwrap_or_default()
+            .trim();
+        let mut items: Vec<CompletionItem> = Vec::new();
+        for (name, detail) in candidates {
+            let is_listed = name != typed && listed.contains(&name.as_str());
+            if !is_listed && !items.iter().any(|it| it.label == name) {
+                items.push(CompletionItem {
+                    label: name,
+                    kind: Some(CompletionItemKind::FIELD),
+                    detail,
+                    ..Default::default()
+                });
+            }
+        }
+        Some(CompletionResponse::Array(items))
+    }
+}
+
+/// Module read from an open file, which may not be saved yet.
+fn module_info(uri: &Url, content: &str) -> ModuleInfo {
+    let path = uri.to_file_path().map_or_else(
+        |_| uri.to_string(),
+        |path| path.to_string_lossy().into_owned(),
+    );
+    let name = Path::new(&path)
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    ModuleInfo {
+        path,
+        parents: Vec::new(),
+        name,
+        content: content.to_string(),
+    }
+}
+
+/// Converts `diagnostic` to LSP diagnostics, located at its first label. Related diagnostics
+/// are converted on their own, as each of them has its own location.
+fn push_diagnostics(
+    diagnostic: &dyn miette::Diagnostic,
+    uri: &Url,
+    text: &str,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    if let Some(related) = diagnostic.related() {
+        for diagnostic in related {
+            push_diagnostics(diagnostic, uri, text, diagnostics);
+        }
+        return;
+    }
+    let mut labels = diagnostic.labels().into_iter().flatten();
+    let primary = labels
+        .next()
+        .map_or_else(Range::default, |label| range(text, label.inner()));
+    let related_information: Vec<_> = labels
+        .map(|label| DiagnosticRelatedInformation {
+            location: Location {
+                uri: uri.clone(),
+                range: range(text, label.inner()),
+            },
+            message: label.label().unwrap_or_default().to_string(),
+        })
+        .collect();
+    let mut message = diagnostic.to_string();
+    if let Some(help) = diagnostic.help() {
+        message.push_str(&format!("\nhelp: {help}"));
+    }
+    let severity = match diagnostic.severity().unwrap_or(Severity::Error) {
+        Severity::Error => DiagnosticSeverity::ERROR,
+        Severity::Warning => DiagnosticSeverity::WARNING,
+        Severity::Advice => DiagnosticSeverity::HINT,
+    };
+    diagnostics.push(Diagnostic {
+        range: primary,
+        severity: Some(severity),
+        code: diagnostic
+            .code()
+            .map(|code| NumberOrString::String(code.to_string())),
+        source: Some(String::from("cornucopia")),
+        message,
+        related_information: (!related_information.is_empty()).then_some(related_information),
+        ..Default::default()
+    });
+}
+
+/// Renders the parameters of a query: their struct if they have one, or the `bind` method.
+fn render_params(params: &PreparedItem, is_async: bool) -> String {
+    let traits = &mut Vec::new();
+    let fields: Vec<_> = params
+        .fields
+        .iter()
+        .map(|field| (&field.name, field.param_ergo_ty(is_async, traits)))
+        .collect();
+    let lifetime = params.is_ref.then(|| String::from("'a"));
+    let generics: Vec<_> = lifetime
+        .into_iter()
+        .chain(
+            traits
+                .iter()
+                .enumerate()
+                .map(|(i, bound)| format!("{}: {bound}", idx_char(i + 1))),
+        )
+        .collect();
+    let generics = if generics.is_empty() {
+        String::new()
+    } else {
+        format!("<{}>", generics.join(", "))
+    };
+    if params.is_named {
+        let fields: String = fields
+            .iter()
+            .map(|(name, ty)| format!("    pub {name}: {ty},\n"))
+            .collect();
+        format!("pub struct {}{generics} {{\n{fields}}}", params.name)
+    } else {
+        let args: Vec<_> = fields
+            .iter()
+            .map(|(name, ty)| format!("{name}: &{ty}"))
+            .collect();
+        format!(
+            "pub fn bind{generics}(&mut self, client: &C, {})",
+            args.join(", ")
+        )
+    }
+}
+
+/// Renders the row of a query: its struct if it has one, or the type of its only column.
+fn render_row(row: &PreparedItem) -> String {
+    if row.is_named {
+        let fields: String = row
+            .fields
+            .iter()
+            .map(|field| format!("    pub {}: {},\n", field.name, field.own_struct()))
+            .collect();
+        format!("pub struct {} {{\n{fields}}}", row.name)
+    } else {
+        row.fields[0].own_struct()
+    }
+}
+
+/// Returns the line numbered `line`, without its line ending, and the offset at which it starts.
+fn line_at(text: &str, line: u32) -> (&str, usize) {
+    let start: usize = text
+        .split_inclusive('\n')
+        .take(line as usize)
+        .map(str::len)
+        .sum();
+    let content = text[start..].lines().next().unwrap_or_default();
+    (content, start)
+}
+
+/// Finds the annotation spanning the line `line`, with its offset in `text`. An annotation may
+/// start on a previous line, as its lists can continue on the next lines.
+fn annotation_at(text: &str, line: u32) -> Option<(Annotation, usize)> {
+    let (_, line_start) = line_at(text, line);
+    for line in (0..=line).rev() {
+        let (content, start) = line_at(text, line);
+        let trimmed = content.trim_start();
+        if trimmed.starts_with("--!") || trimmed.starts_with("--:") {
+            let start = start + content.len() - trimmed.len();
+            let (annotation, len) = parse_annotation_at(&text[start..])?;
+            return (start + len >= line_start).then_some((annotation, start));
+        } else if !trimmed.starts_with("--") {
+            return None;
+        }
+    }
+    None
+}
+
+/// Converts a span counting the chars of `text` to a span counting its bytes.
+fn char_span(text: &str, span: &SourceSpan) -> SourceSpan {
+    let byte_offset = |offset: usize| {
+        text.char_indices()
+            .nth(offset)
+            .map_or(text.len(), |(i, _)| i)
+    };
+    let start = byte_offset(span.offset());
+    (start..byte_offset(span.offset() + span.len())).into()
+}
+
+/// Converts an LSP position, whose columns count UTF-16 code units, to an offset in `text`.
+fn offset(text: &str, position: Position) -> usize {
+    let (line, start) = line_at(text, position.line);
+    let mut units = 0;
+    for (i, c) in line.char_indices() {
+        if units >= position.character as usize {
+            return start + i;
+        }
+        units += c.len_utf16();
+    }
+    start + line.len()
+}
+
+/// Converts an offset in `text` to an LSP position.
+fn position(text: &str, offset: usize) -> Position {
+    let mut offset = offset.min(text.len());
+    while !text.is_char_boundary(offset) {
+        offset -= 1;
+    }
+    let before = &text[..offset];
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    Position::new(
+        before.matches('\n').count() as u32,
+        before[line_start..].encode_utf16().count() as u32,
+    )
+}
+
+fn range(text: &str, span: &SourceSpan) -> Range {
+    Range::new(
+        position(text, span.offset()),
+        position(text, span.offset() + span.len()),
+    )
+}
+
+fn contains(span: &SourceSpan, offset: usize) -> bool {
+    (span.offset()..=span.offset() + span.len()).contains(&offset)
+}
+
+pub(crate) mod error {
+    use miette::Diagnostic;
+    use thiserror::Error as ThisError;
+
+    #[derive(Debug, ThisError, Diagnostic)]
+    pub enum Error {
+        #[error("Language server protocol error: {0}")]
+        Protocol(#[from] lsp_server::ProtocolError),
+        #[error("Could not communicate with the editor: ({0})")]
+        Io(#[from] std::io::Error),
+        #[error("The editor closed the connection")]
+        Disconnected,
+    }
+}
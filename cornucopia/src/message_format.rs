@@ -0,0 +1,105 @@
+use std::io::Write;
+
+use miette::{Diagnostic, Severity, SourceCode, SourceSpan};
+use serde_json::{json, Value};
+
+/// How the CLI prints diagnostics.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub(crate) enum MessageFormat {
+    /// Graphical reports on the standard error
+    #[default]
+    Human,
+    /// One JSON object per diagnostic and per line on the standard output. Other output, like
+    /// the diffs of `check` and `fmt --check`, goes to the standard error
+    Json,
+}
+
+impl MessageFormat {
+    /// Prints a status message, kept out of the standard output when it holds JSON.
+    pub(crate) fn status(self, message: &str) {
+        match self {
+            Self::Human => println!("{message}"),
+            Self::Json => eprintln!("{message}"),
+        }
+    }
+
+    /// Output of the diffs printed when checking files, kept out of the standard output
+    /// when it holds JSON.
+    pub(crate) fn diff_output(self) -> Box<dyn Write> {
+        match self {
+            Self::Human => Box::new(std::io::stdout().lock()),
+            Self::Json => Box::new(std::io::stderr().lock()),
+        }
+    }
+
+    /// Prints a diagnostic, like an error or a warning.
+    pub(crate) fn print(self, diagnostic: impl Diagnostic + Send + Sync + 'static) {
+        match self {
+            Self::Human => eprintln!("{:?}", miette::Report::new(diagnostic)),
+            Self::Json => print!("{}", render_json(&diagnostic)),
+        }
+    }
+}
+
+/// Renders `diagnostic` as JSON lines. Related diagnostics are rendered on their own lines
+/// instead of their parent, as each of them has its own location.
+///
+/// Each line holds the `severity`, `code`, `message` and `help` of a diagnostic, the `file`
+/// it was found in, and its `labels`. The primary `span` is the one of the first label.
+/// Spans hold their `start` and `end` byte offsets and the `line` and `column` at which
+/// they start, counted from 1, columns in bytes.
+pub(crate) fn render_json(diagnostic: &dyn Diagnostic) -> String {
+    let mut lines = String::new();
+    push_json(diagnostic, &mut lines);
+    lines
+}
+
+fn push_json(diagnostic: &dyn Diagnostic, lines: &mut String) {
+    if let Some(related) = diagnostic.related() {
+        for diagnostic in related {
+            push_json(diagnostic, lines);
+        }
+        return;
+    }
+    let source = diagnostic.source_code();
+    let mut file = None;
+    let labels: Vec<_> = diagnostic
+        .labels()
+        .into_iter()
+        .flatten()
+        .map(|label| {
+            let (name, span) = locate(source, label.inner());
+            file = file.take().or(name);
+            json!({ "message": label.label(), "span": span })
+        })
+        .collect();
+    let severity = match diagnostic.severity().unwrap_or(Severity::Error) {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Advice => "advice",
+    };
+    let line = json!({
+        "severity": severity,
+        "code": diagnostic.code().map(|code| code.to_string()),
+        "message": diagnostic.to_string(),
+        "file": file,
+        "span": labels.first().map_or(Value::Null, |label| label["span"].clone()),
+        "labels": labels,
+        "help": diagnostic.help().map(|help| help.to_string()),
+    });
+    lines.push_str(&line.to_string());
+    lines.push('\n');
+}
+
+/// Returns the name of the file containing `span`, and `span` as JSON.
+fn locate(source: Option<&dyn SourceCode>, span: &SourceSpan) -> (Option<String>, Value) {
+    let contents = source.and_then(|source| source.read_span(span, 0, 0).ok());
+    let span_json = json!({
+        "start": span.offset(),
+        "end": span.offset() + span.len(),
+        "line": contents.as_ref().map(|contents| contents.line() + 1),
+        "column": contents.as_ref().map(|contents| contents.column() + 1),
+    });
+    let name = contents.and_then(|contents| contents.name().map(String::from));
+    (name, span_json)
+}
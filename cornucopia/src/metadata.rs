@@ -0,0 +1,455 @@
+use std::collections::{BTreeMap, HashMap};
+
+use postgres::Client;
+use postgres_types::{Field, Kind, Type};
+use serde::{Deserialize, Serialize};
+
+use self::error::Error;
+
+/// A column returned by a prepared statement.
+#[derive(Debug, Clone)]
+pub(crate) struct Column {
+    name: String,
+    ty: Type,
+    /// Whether this column comes from a table column declared `NOT NULL`
+    not_null: bool,
+    /// The comment of the table column this column comes from
+    comment: Option<String>,
+}
+
+impl Column {
+    pub(crate) fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub(crate) fn type_(&self) -> &Type {
+        &self.ty
+    }
+
+    pub(crate) fn not_null(&self) -> bool {
+        self.not_null
+    }
+
+    pub(crate) fn comment(&self) -> Option<&str> {
+        self.comment.as_deref()
+    }
+}
+
+/// Parameters and columns types of a prepared statement.
+#[derive(Debug, Clone)]
+pub(crate) struct Stmt {
+    pub(crate) params: Vec<Type>,
+    pub(crate) columns: Vec<Column>,
+}
+
+impl From<postgres::Statement> for Stmt {
+    fn from(stmt: postgres::Statement) -> Self {
+        Self {
+            params: stmt.params().to_vec(),
+            columns: stmt
+                .columns()
+                .iter()
+                .map(|c| Column {
+                    name: c.name().to_string(),
+                    ty: c.type_().clone(),
+                    not_null: false,
+                    comment: None,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// `COMMENT ON` a custom type and its members.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct TypeComments {
+    pub(crate) comment: Option<String>,
+    /// Comments of composite attributes, by name. Enum values can't have comments.
+    pub(crate) members: BTreeMap<String, String>,
+}
+
+/// Prepared statements metadata, indexed by their SQL,
+/// and the comments of the custom types they use, indexed by schema and name.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Metadata {
+    stmts: BTreeMap<String, Stmt>,
+    comments: BTreeMap<(String, String), TypeComments>,
+}
+
+impl Metadata {
+    /// Reads metadata previously written with `Metadata::write`.
+    pub(crate) fn read(path: &str) -> Result<Self, Error> {
+        let content = std::fs::read_to_string(path).map_err(|err| Error::Io {
+            path: path.to_string(),
+            err,
+        })?;
+        let file: MetadataFile = serde_json::from_str(&content).map_err(|err| Error::Parse {
+            path: path.to_string(),
+            err,
+        })?;
+        file.into_metadata().map_err(|ty| Error::UnknownType {
+            path: path.to_string(),
+            ty,
+        })
+    }
+
+    /// Writes metadata as pretty JSON, in a deterministic order.
+    pub(crate) fn write(&self, path: &str) -> Result<(), Error> {
+        let file = MetadataFile::from_metadata(self);
+        let mut json = serde_json::to_string_pretty(&file).expect("metadata is serializable");
+        json.push('\n');
+        std::fs::write(path, json).map_err(|err| Error::Io {
+            path: path.to_string(),
+            err,
+        })
+    }
+
+    /// Returns the comments of a custom type, if it has any.
+    pub(crate) fn type_comments(&self, ty: &Type) -> Option<&TypeComments> {
+        self.comments
+            .get(&(ty.schema().to_string(), ty.name().to_string()))
+    }
+}
+
+/// Where statements are prepared.
+///
+/// Statements prepared against a database are recorded, so that their metadata
+/// can be written and later used without a database.
+pub(crate) struct StmtSource<'a> {
+    client: Option<&'a mut Client>,
+    metadata: Metadata,
+}
+
+impl<'a> StmtSource<'a> {
+    /// Prepare statements against a live database.
+    pub(crate) fn live(client: &'a mut Client) -> Self {
+        Self {
+            client: Some(client),
+            metadata: Metadata::default(),
+        }
+    }
+
+    /// Read statements from previously recorded metadata.
+    pub(crate) fn offline(metadata: Metadata) -> Self {
+        Self {
+            client: None,
+            metadata,
+        }
+    }
+
+    /// Prepares `sql`. Returns `None` if the statement is missing from offline metadata.
+    pub(crate) fn prepare(&mut self, sql: &str) -> Result<Option<Stmt>, postgres::Error> {
+        if let Some(client) = &mut self.client {
+            let stmt = client.prepare(sql)?;
+            // Nullity and comment of the table columns, fetched at once
+            let keys: Vec<_> = stmt
+                .columns()
+                .iter()
+                .map(|column| column.table_oid().zip(column.column_id()))
+                .collect();
+            let (tables, ids): (Vec<u32>, Vec<i16>) = keys.iter().flatten().copied().unzip();
+            let table_columns: HashMap<(u32, i16), (bool, Option<String>)> = if tables.is_empty() {
+                HashMap::new()
+            } else {
+                client
+                    .query(
+                        "SELECT a.attrelid, a.attnum, a.attnotnull, col_description(a.attrelid, a.attnum) \
+                        FROM pg_attribute a JOIN unnest($1::oid[], $2::int2[]) c(relid, num) \
+                        ON a.attrelid = c.relid AND a.attnum = c.num",
+                        &[&tables, &ids],
+                    )?
+                    .iter()
+                    .map(|row| ((row.get(0), row.get(1)), (row.get(2), row.get(3))))
+                    .collect()
+            };
+            let mut stmt = Stmt::from(stmt);
+            for (column, key) in stmt.columns.iter_mut().zip(keys) {
+                if let Some((not_null, comment)) = key.and_then(|key| table_columns.get(&key)) {
+                    column.not_null = *not_null;
+                    column.comment = comment.clone();
+                }
+            }
+            self.metadata.stmts.insert(sql.to_string(), stmt);
+        }
+        Ok(self.metadata.stmts.get(sql).cloned())
+    }
+
+    pub(crate) fn metadata(&self) -> &Metadata {
+        &self.metadata
+    }
+
+    /// Records the comments of a custom type `ty` and of the custom types it contains, when
+    /// it is registered. Without a database, they come from the metadata.
+    pub(crate) fn record_type_comments(&mut self, ty: &Type) -> Result<(), postgres::Error> {
+        match &mut self.client {
+            Some(client) => Self::record_comments(client, &mut self.metadata.comments, ty),
+            None => Ok(()),
+        }
+    }
+
+    fn record_comments(
+        client: &mut Client,
+        comments: &mut BTreeMap<(String, String), TypeComments>,
+        ty: &Type,
+    ) -> Result<(), postgres::Error> {
+        if Type::from_oid(ty.oid()).is_some() {
+            return Ok(());
+        }
+        let key = (ty.schema().to_string(), ty.name().to_string());
+        if comments.contains_key(&key) {
+            return Ok(());
+        }
+        let members_query = match ty.kind() {
+            Kind::Array(inner) => return Self::record_comments(client, comments, inner),
+            Kind::Domain(inner) => {
+                Self::record_comments(client, comments, inner)?;
+                None
+            }
+            Kind::Composite(fields) => {
+                for field in fields {
+                    Self::record_comments(client, comments, field.type_())?;
+                }
+                Some(
+                    "SELECT a.attname::text, d.description FROM pg_type t \
+                    JOIN pg_attribute a ON a.attrelid = t.typrelid \
+                    JOIN pg_description d ON d.classoid = 'pg_class'::regclass AND d.objoid = a.attrelid AND d.objsubid = a.attnum \
+                    WHERE t.oid = $1",
+                )
+            }
+            _ => None,
+        };
+        let comment = client
+            .query_one("SELECT obj_description($1, 'pg_type')", &[&ty.oid()])?
+            .get(0);
+        let members = match members_query {
+            Some(query) => client
+                .query(query, &[&ty.oid()])?
+                .iter()
+                .map(|row| (row.get(0), row.get(1)))
+                .collect(),
+            None => BTreeMap::new(),
+        };
+        comments.insert(key, TypeComments { comment, members });
+        Ok(())
+    }
+}
+
+/// On-disk representation of `Metadata`.
+#[derive(Serialize, Deserialize)]
+struct MetadataFile {
+    types: Vec<TypeDef>,
+    statements: BTreeMap<String, StmtDef>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct StmtDef {
+    params: Vec<TypeRef>,
+    columns: Vec<ColumnDef>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ColumnDef {
+    name: String,
+    #[serde(rename = "type")]
+    ty: TypeRef,
+    #[serde(default, skip_serializing_if = "is_false")]
+    not_null: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    comment: Option<String>,
+}
+
+fn is_false(value: &bool) -> bool {
+    !value
+}
+
+/// Built-in types are referenced by their oid and custom types by their schema and name,
+/// as custom types oid are not stable between databases.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(untagged)]
+enum TypeRef {
+    Builtin(u32),
+    Custom(String, String),
+}
+
+#[derive(Serialize, Deserialize)]
+struct TypeDef {
+    schema: String,
+    name: String,
+    kind: KindDef,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    comment: Option<String>,
+    /// Comments of composite attributes
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    member_comments: BTreeMap<String, String>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum KindDef {
+    Simple,
+    Enum(Vec<String>),
+    Array(TypeRef),
+    Domain(TypeRef),
+    Composite(Vec<ColumnDef>),
+}
+
+impl MetadataFile {
+    fn from_metadata(metadata: &Metadata) -> Self {
+        let mut types = BTreeMap::new();
+        let statements = metadata
+            .stmts
+            .iter()
+            .map(|(sql, stmt)| {
+                let def = StmtDef {
+                    params: stmt
+                        .params
+                        .iter()
+                        .map(|ty| Self::type_ref(ty, &mut types))
+                        .collect(),
+                    columns: stmt
+                        .columns
+                        .iter()
+                        .map(|c| ColumnDef {
+                            name: c.name.clone(),
+                            ty: Self::type_ref(&c.ty, &mut types),
+                            not_null: c.not_null,
+                            comment: c.comment.clone(),
+                        })
+                        .collect(),
+                };
+                (sql.clone(), def)
+            })
+            .collect();
+        Self {
+            types: types
+                .into_iter()
+                .map(|(key, kind)| {
+                    let comments = metadata.comments.get(&key).cloned().unwrap_or_default();
+                    let (schema, name) = key;
+                    TypeDef {
+                        schema,
+                        name,
+                        kind,
+                        comment: comments.comment,
+                        member_comments: comments.members,
+                    }
+                })
+                .collect(),
+            statements,
+        }
+    }
+
+    /// Registers custom types definitions and returns a reference to `ty`
+    fn type_ref(ty: &Type, types: &mut BTreeMap<(String, String), KindDef>) -> TypeRef {
+        if Type::from_oid(ty.oid()).is_some() {
+            return TypeRef::Builtin(ty.oid());
+        }
+        let key = (ty.schema().to_string(), ty.name().to_string());
+        if !types.contains_key(&key) {
+            // Unsupported kinds are kept as simple types, they will be reported during type registration
+            let kind = match ty.kind() {
+                Kind::Enum(variants) => KindDef::Enum(variants.clone()),
+                Kind::Array(inner) => KindDef::Array(Self::type_ref(inner, types)),
+                Kind::Domain(inner) => KindDef::Domain(Self::type_ref(inner, types)),
+                Kind::Composite(fields) => KindDef::Composite(
+                    fields
+                        .iter()
+                        .map(|f| ColumnDef {
+                            name: f.name().to_string(),
+                            ty: Self::type_ref(f.type_(), types),
+                            not_null: false,
+                            comment: None,
+                        })
+                        .collect(),
+                ),
+                _ => KindDef::Simple,
+            };
+            types.insert(key.clone(), kind);
+        }
+        TypeRef::Custom(key.0, key.1)
+    }
+
+    /// Rebuilds metadata, returns the name of the first type that cannot be resolved on failure.
+    fn into_metadata(self) -> Result<Metadata, String> {
+        let defs: HashMap<_, _> = self
+            .types
+            .iter()
+            .map(|def| ((def.schema.as_str(), def.name.as_str()), def))
+            .collect();
+        let resolve = |ty: &TypeRef| Self::resolve(ty, &defs);
+        let mut stmts = BTreeMap::new();
+        for (sql, def) in self.statements {
+            let params = def.params.iter().map(resolve).collect::<Result<_, _>>()?;
+            let columns = def
+                .columns
+                .iter()
+                .map(|c| {
+                    Ok(Column {
+                        name: c.name.clone(),
+                        ty: resolve(&c.ty)?,
+                        not_null: c.not_null,
+                        comment: c.comment.clone(),
+                    })
+                })
+                .collect::<Result<_, String>>()?;
+            stmts.insert(sql, Stmt { params, columns });
+        }
+        let comments = self
+            .types
+            .into_iter()
+            .map(|def| {
+                let comments = TypeComments {
+                    comment: def.comment,
+                    members: def.member_comments,
+                };
+                ((def.schema, def.name), comments)
+            })
+            .collect();
+        Ok(Metadata { stmts, comments })
+    }
+
+    fn resolve(ty: &TypeRef, defs: &HashMap<(&str, &str), &TypeDef>) -> Result<Type, String> {
+        match ty {
+            TypeRef::Builtin(oid) => Type::from_oid(*oid).ok_or_else(|| format!("oid {oid}")),
+            TypeRef::Custom(schema, name) => {
+                let def = defs
+                    .get(&(schema.as_str(), name.as_str()))
+                    .ok_or_else(|| format!("{schema}.{name}"))?;
+                let kind = match &def.kind {
+                    KindDef::Simple => Kind::Simple,
+                    KindDef::Enum(variants) => Kind::Enum(variants.clone()),
+                    KindDef::Array(inner) => Kind::Array(Self::resolve(inner, defs)?),
+                    KindDef::Domain(inner) => Kind::Domain(Self::resolve(inner, defs)?),
+                    KindDef::Composite(fields) => Kind::Composite(
+                        fields
+                            .iter()
+                            .map(|f| Ok(Field::new(f.name.clone(), Self::resolve(&f.ty, defs)?)))
+                            .collect::<Result<_, String>>()?,
+                    ),
+                };
+                // Custom types oid are not recorded, codegen only relies on their schema and name
+                Ok(Type::new(name.clone(), 0, kind, schema.clone()))
+            }
+        }
+    }
+}
+
+pub(crate) mod error {
+    use miette::Diagnostic;
+    use thiserror::Error as ThisError;
+
+    #[derive(Debug, ThisError, Diagnostic)]
+    pub enum Error {
+        #[error("Could not access metadata file `{path}`: ({err})")]
+        Io { path: String, err: std::io::Error },
+        #[error("Could not parse metadata file `{path}`: ({err})")]
+        #[diagnostic(help("regenerate this file against a database"))]
+        Parse {
+            path: String,
+            err: serde_json::Error,
+        },
+        #[error("Metadata file `{path}` references an unknown type `{ty}`")]
+        #[diagnostic(help("regenerate this file against a database"))]
+        UnknownType { path: String, ty: String },
+    }
+}
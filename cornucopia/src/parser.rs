@@ -76,6 +76,20 @@ fn space() -> impl Parser<char, (), Error = Simple<char>> {
         .ignored()
 }
 
+/// Spaces inside an open list of an annotation, which may continue on the next `--` line.
+/// Outside of a list, a `--` line is an ordinary comment.
+fn annotation_space() -> impl Parser<char, (), Error = Simple<char>> {
+    let continuation = filter(|c: &char| *c == '\n')
+        .then(space())
+        .then(just("--"))
+        .then(none_of(":!").rewind());
+    filter(|c: &char| c.is_whitespace() && *c != '\n')
+        .ignored()
+        .or(continuation.ignored())
+        .repeated()
+        .ignored()
+}
+
 fn blank() -> impl Parser<char, (), Error = Simple<char>> {
     // We want to escape valid SQL comment beginning with -- while not escaping our syntax --: or --!
     let comment = just("--")
@@ -84,10 +98,101 @@ fn blank() -> impl Parser<char, (), Error = Simple<char>> {
     filter(|c: &char| c.is_whitespace())
         .ignored()
         .or(comment.ignored())
+        .or(block_comment().ignored())
         .repeated()
         .ignored()
 }
 
+/// A `/* */` comment, which may contain nested comments.
+fn block_comment() -> impl Parser<char, String, Error = Simple<char>> {
+    recursive(|block| {
+        let content = block
+            .or(just('*')
+                .then_ignore(none_of('/').rewind())
+                .map(String::from))
+            .or(just('/')
+                .then_ignore(none_of('*').rewind())
+                .map(String::from))
+            .or(none_of("*/").map(String::from));
+        just("/*")
+            .ignore_then(content.repeated())
+            .then_ignore(just("*/"))
+            .map(|content| format!("/*{}*/", content.concat()))
+    })
+}
+
+/// A piece of SQL: a named parameter, or text copied as is.
+enum SqlChunk {
+    Bind(Span<String>),
+    Text(String),
+}
+
+/// Lexes SQL just enough to find its named parameters, like `:id`, and its terminating `;`.
+/// Neither has any meaning inside string constants, quoted identifiers and comments, and
+/// casts like `::text` are not parameters.
+fn sql_chunk() -> impl Parser<char, SqlChunk, Error = Simple<char>> {
+    // https://www.postgresql.org/docs/current/sql-syntax-lexical.html
+
+    // ::cast
+    let cast = just("::").map(String::from);
+    // :bind
+    let bind = just(':').ignore_then(ident());
+    // "quoted:identifier;"
+    let quoted_ident = just('"')
+        .chain(none_of('"').repeated())
+        .chain(just('"'))
+        .collect();
+    // ':string;', quotes being escaped by doubling them, which reads as two strings
+    let string = just('\'')
+        .chain(none_of('\'').repeated())
+        .chain(just('\''))
+        .collect();
+    // E'\':string;\''
+    let c_style_escape = just('\\')
+        .chain(any())
+        .or(just('\'').chain(just('\'')))
+        .or(none_of('\'').map(|c| vec![c]));
+    let c_style_string = one_of("eE")
+        .chain(just('\''))
+        .chain(c_style_escape.repeated().flatten())
+        .chain(just('\''))
+        .collect();
+    // $tag$:dollar;quoted$tag$, the tag being optional
+    let tag = filter(|c: &char| c.is_alphabetic() || *c == '_')
+        .chain(filter(|c: &char| c.is_alphanumeric() || *c == '_').repeated())
+        .or_not()
+        .flatten();
+    let dollar_quoted = just('$')
+        .chain(tag)
+        .chain(just('$'))
+        .collect::<String>()
+        .then_with(|delimiter| {
+            take_until(just(delimiter.clone())).map(move |(content, _)| {
+                format!("{delimiter}{}{delimiter}", String::from_iter(content))
+            })
+        });
+    // -- comment;
+    let line_comment = just("--")
+        .ignore_then(none_of('\n').repeated())
+        .map(|content| format!("--{}", String::from_iter(content)));
+    // Keywords and identifiers, so that a string prefix is only read at their start
+    let word = filter(|c: &char| c.is_alphanumeric() || *c == '_')
+        .chain(filter(|c: &char| c.is_alphanumeric() || *c == '_' || *c == '$').repeated())
+        .collect();
+
+    let text = c_style_string
+        .or(quoted_ident)
+        .or(string)
+        .or(dollar_quoted)
+        .or(line_comment)
+        .or(block_comment())
+        .or(word)
+        .or(none_of(';').map(String::from));
+    cast.map(SqlChunk::Text)
+        .or(bind.map(SqlChunk::Bind))
+        .or(text.map(SqlChunk::Text))
+}
+
 #[derive(Debug, Clone)]
 pub struct NullableIdent {
     pub name: Span<String>,
@@ -96,7 +201,7 @@ pub struct NullableIdent {
 }
 
 fn parse_nullable_ident() -> impl Parser<char, Vec<NullableIdent>, Error = Simple<char>> {
-    space()
+    annotation_space()
         .ignore_then(ident())
         .then(just('?').or_not())
         .then(just("[?]").or_not())
@@ -105,16 +210,55 @@ fn parse_nullable_ident() -> impl Parser<char, Vec<NullableIdent>, Error = Simpl
             nullable: null.is_some(),
             inner_nullable: inner_null.is_some(),
         })
-        .then_ignore(space())
+        .then_ignore(annotation_space())
         .separated_by(just(','))
         .allow_trailing()
+        .then_ignore(annotation_space())
         .delimited_by(just('('), just(')'))
 }
 
+/// An attribute of a query, like `deprecated` or `deprecated = "note"`.
+#[derive(Debug, Clone)]
+pub(crate) struct Attribute {
+    pub(crate) name: Span<String>,
+    pub(crate) value: Option<Span<String>>,
+}
+
+impl Attribute {
+    /// Parse an attribute list, like `#[deprecated, name = "value"]`
+    fn parser() -> impl Parser<char, Vec<Self>, Error = Simple<char>> {
+        let value = none_of('"')
+            .repeated()
+            .collect::<String>()
+            .delimited_by(just('"'), just('"'))
+            .map_with_span(|value, span: Range<usize>| Span {
+                value,
+                span: span.into(),
+            });
+        annotation_space()
+            .ignore_then(ident())
+            .then(
+                annotation_space()
+                    .ignore_then(just('='))
+                    .ignore_then(annotation_space())
+                    .ignore_then(value)
+                    .or_not(),
+            )
+            .map(|(name, value)| Self { name, value })
+            .then_ignore(annotation_space())
+            .separated_by(just(','))
+            .allow_trailing()
+            .then_ignore(annotation_space())
+            .delimited_by(just("#["), just(']'))
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct TypeAnnotation {
     pub name: Span<String>,
     pub fields: Vec<NullableIdent>,
+    /// The comment lines directly above the annotation
+    pub doc: Vec<String>,
 }
 
 impl TypeAnnotation {
@@ -124,7 +268,11 @@ impl TypeAnnotation {
             .ignore_then(ident())
             .then_ignore(space())
             .then(parse_nullable_ident())
-            .map(|(name, fields)| Self { name, fields })
+            .map(|(name, fields)| Self {
+                name,
+                fields,
+                doc: Vec::new(),
+            })
     }
 }
 
@@ -136,108 +284,73 @@ pub(crate) struct Query {
     pub(crate) sql_span: SourceSpan,
     pub(crate) sql_str: String,
     pub(crate) bind_params: Vec<Span<String>>,
+    pub(crate) attributes: Vec<Attribute>,
+    /// The comment lines directly above the annotation
+    pub(crate) doc: Vec<String>,
 }
 
 impl Query {
-    /// Escape sql string and pattern that are not bind
-    fn sql_escaping() -> impl Parser<char, (), Error = Simple<char>> {
-        // https://www.postgresql.org/docs/current/sql-syntax-lexical.html
-
-        // ::bind
-        let cast = just("::").ignored();
-        // ":bind" TODO is this possible ?
-        let constant = none_of("\"")
-            .repeated()
-            .delimited_by(just("\""), just("\""))
-            .ignored();
-        // ':bind'
-        let string = none_of("'")
-            .repeated()
-            .delimited_by(just("'"), just("'"))
-            .ignored();
-        // E'\':bind\''
-        let c_style_string = just("\\'")
-            .or(just("''"))
-            .ignored()
-            .or(none_of("'").ignored())
-            .repeated()
-            .delimited_by(just("e'").or(just("E'")), just("'"))
-            .ignored();
-        // $:bind$:bind$:bind$
-        let dollar_tag = just("$").then(none_of("$").repeated()).then(just("$"));
-        let dollar_quoted = none_of("$")
-            .repeated()
-            .delimited_by(dollar_tag.clone(), dollar_tag)
-            .ignored();
-
-        c_style_string
-            .or(cast)
-            .or(string)
-            .or(constant)
-            .or(dollar_quoted)
-            // Non c_style_string e
-            .or(one_of("eE").then(none_of("'").rewind()).ignored())
-            // Non binding sql
-            .or(none_of("\"':$eE").ignored())
-            .repeated()
-            .at_least(1)
-            .ignored()
-    }
-
-    /// Parse all bind from an SQL query
-    fn parse_bind() -> impl Parser<char, Vec<Span<String>>, Error = Simple<char>> {
-        just(':')
-            .ignore_then(ident())
-            .separated_by(Self::sql_escaping())
-            .allow_leading()
-            .allow_trailing()
-    }
-
-    /// Parse sql query, normalizing named parameters
+    /// Parse the sql query up to its `;`, normalizing named parameters
     fn parse_sql_query(
     ) -> impl Parser<char, (String, SourceSpan, Vec<Span<String>>), Error = Simple<char>> {
-        none_of(";")
+        sql_chunk()
             .repeated()
             .then_ignore(just(';'))
-            .collect::<String>()
-            .map_with_span(|mut sql_str, span: Range<usize>| {
-                let bind_params: Vec<_> = Self::parse_bind().parse(sql_str.clone()).unwrap();
-                // Remove duplicate
-                let dedup_params: Vec<_> = bind_params
-                    .iter()
-                    .enumerate()
-                    .rev()
-                    .filter_map(|(i, u)| (!bind_params[..i].contains(u)).then(|| u.clone()))
-                    .rev()
-                    .collect();
-
-                for bind_param in bind_params.iter().rev() {
-                    let index = dedup_params.iter().position(|bp| bp == bind_param).unwrap();
-                    let start = bind_param.span.offset() - 1;
-                    let end = start + bind_param.span.len();
-                    sql_str.replace_range(start..=end, &format!("${}", index + 1));
+            .map_with_span(|chunks, span: Range<usize>| {
+                let mut sql_str = String::new();
+                let mut bind_params: Vec<Span<String>> = Vec::new();
+                for chunk in chunks {
+                    match chunk {
+                        SqlChunk::Text(text) => sql_str.push_str(&text),
+                        SqlChunk::Bind(bind) => {
+                            // A parameter used several times keeps its first position
+                            let index = match bind_params.iter().position(|it| *it == bind) {
+                                Some(index) => index,
+                                None => {
+                                    bind_params.push(bind);
+                                    bind_params.len() - 1
+                                }
+                            };
+                            sql_str.push_str(&format!("${}", index + 1));
+                        }
+                    }
                 }
-
-                (sql_str, span.into(), dedup_params)
+                (sql_str, span.into(), bind_params)
             })
     }
 
-    fn parse_query_annotation(
-    ) -> impl Parser<char, (Span<String>, QueryDataStruct, QueryDataStruct), Error = Simple<char>>
-    {
+    /// Parse a query annotation. Its lists may continue on the next `--` lines until closed.
+    fn parse_query_annotation() -> impl Parser<
+        char,
+        (
+            Span<String>,
+            QueryDataStruct,
+            QueryDataStruct,
+            Vec<Attribute>,
+        ),
+        Error = Simple<char>,
+    > {
         just("--!")
             .ignore_then(space())
             .ignore_then(ident())
             .then_ignore(space())
             .then(QueryDataStruct::parser())
-            .then_ignore(space())
             .then(
-                just(':')
+                space()
+                    .ignore_then(just(':'))
                     .ignore_then(space())
                     .ignore_then(QueryDataStruct::parser())
                     .or_not(),
             )
-            .map(|((name, param), row)| (name, param, row.unwrap_or_default()))
+            .then(space().ignore_then(Attribute::parser()).or_not())
+            .map(|(((name, param), row), attributes)| {
+                (
+                    name,
+                    param,
+                    row.unwrap_or_default(),
+                    attributes.unwrap_or_default(),
+                )
+            })
     }
 
     fn parser() -> impl Parser<char, Self, Error = Simple<char>> {
@@ -246,13 +359,15 @@ impl Query {
             .then_ignore(ln())
             .then(Self::parse_sql_query())
             .map(
-                |((name, param, row), (sql_str, sql_span, bind_params))| Self {
+                |((name, param, row, attributes), (sql_str, sql_span, bind_params))| Self {
                     name,
                     param,
                     row,
                     sql_span,
                     sql_str,
                     bind_params,
+                    attributes,
+                    doc: Vec::new(),
                 },
             )
     }
@@ -336,10 +451,72 @@ impl QueryDataStruct {
     }
 }
 
+/// A `--!` or `--:` annotation parsed on its own, with spans relative to its start.
+#[derive(Debug)]
+pub(crate) enum Annotation {
+    Type(TypeAnnotation),
+    Query {
+        name: Span<String>,
+        param: QueryDataStruct,
+        row: QueryDataStruct,
+        attributes: Vec<Attribute>,
+    },
+}
+
+/// Parses an annotation on its own.
+fn parse_annotation(line: &str) -> Option<Annotation> {
+    TypeAnnotation::parser()
+        .map(Annotation::Type)
+        .or(
+            Query::parse_query_annotation().map(|(name, param, row, attributes)| {
+                Annotation::Query {
+                    name,
+                    param,
+                    row,
+                    attributes,
+                }
+            }),
+        )
+        .then_ignore(space())
+        .then_ignore(end())
+        .parse(line.trim_end())
+        .ok()
+}
+
+/// Parses the annotation at the start of `text`, for editor features working on files that
+/// may not parse as a whole. The lists of the annotation may continue on the next lines.
+/// Returns it with the length of its text, in bytes.
+pub(crate) fn parse_annotation_at(text: &str) -> Option<(Annotation, usize)> {
+    let mut start = 0;
+    for line in text.split_inclusive('\n') {
+        let trimmed = line.trim_start();
+        let is_continuation =
+            trimmed.starts_with("--") && !trimmed.starts_with("--!") && !trimmed.starts_with("--:");
+        if start > 0 && !is_continuation {
+            break;
+        }
+        let end = start + line.trim_end().len();
+        if let Some(annotation) = parse_annotation(&text[..end]) {
+            return Some((annotation, end));
+        }
+        start += line.len();
+    }
+    None
+}
+
+/// Parses the SQL query at the start of `text` up to its `;`, for editor features working on
+/// files that may not parse as a whole. Returns its normalized SQL and its named parameters.
+pub(crate) fn parse_sql_at(text: &str) -> Option<(String, Vec<Span<String>>)> {
+    Query::parse_sql_query()
+        .map(|(sql_str, _, bind_params)| (sql_str, bind_params))
+        .parse(text)
+        .ok()
+}
+
 #[derive(Debug)]
 enum Statement {
     Type(TypeAnnotation),
-    Query(Query),
+    Query(Box<Query>),
 }
 
 #[derive(Debug)]
@@ -349,23 +526,34 @@ pub(crate) struct Module {
     pub(crate) queries: Vec<Query>,
 }
 
-pub(crate) fn parse_query_module(info: ModuleInfo) -> Result<Module, Error> {
-    match TypeAnnotation::parser()
+/// Parses a module, reporting all its syntax errors.
+///
+/// After an invalid statement, parsing resumes at the next statement, so that
+/// the errors of the following statements are also reported.
+pub(crate) fn parse_query_module(info: ModuleInfo) -> Result<Module, Vec<Error>> {
+    let (statements, errors) = TypeAnnotation::parser()
         .map(Statement::Type)
-        .or(Query::parser().map(Statement::Query))
+        .or(Query::parser().map(|it| Statement::Query(Box::new(it))))
+        .recover_with(skip_then_retry_until([]))
         .separated_by(blank())
         .allow_leading()
         .allow_trailing()
         .then_ignore(end())
-        .parse(info.content.as_str())
-    {
-        Ok(statements) => {
+        .parse_recovery(info.content.as_str());
+    match statements {
+        Some(statements) if errors.is_empty() => {
             let mut types = Vec::new();
             let mut queries = Vec::new();
             for item in statements {
                 match item {
-                    Statement::Type(it) => types.push(it),
-                    Statement::Query(it) => queries.push(it),
+                    Statement::Type(mut it) => {
+                        it.doc = doc_comment(&info.content, it.name.span);
+                        types.push(it);
+                    }
+                    Statement::Query(mut it) => {
+                        it.doc = doc_comment(&info.content, it.name.span);
+                        queries.push(*it);
+                    }
                 }
             }
             Ok(Module {
@@ -374,20 +562,49 @@ pub(crate) fn parse_query_module(info: ModuleInfo) -> Result<Module, Error> {
                 queries,
             })
         }
-        Err(e) => Err(Error {
-            src: info.into(),
-            err_span: e[0].span().into(),
-            help: e[0].to_string().replace('\n', "\\n"),
-        }),
+        _ => Err(errors
+            .into_iter()
+            .map(|e| Error {
+                src: (&info).into(),
+                err_span: e.span().into(),
+                help: e.to_string().replace('\n', "\\n"),
+            })
+            .collect()),
     }
 }
 
+/// Returns the `--` comment lines directly above the annotation containing `span`,
+/// without their `--` and the space following it.
+fn doc_comment(content: &str, span: SourceSpan) -> Vec<String> {
+    // Spans count chars, not bytes
+    let start = content
+        .char_indices()
+        .nth(span.offset())
+        .map_or(content.len(), |(i, _)| i);
+    let line_start = content[..start].rfind('\n').map_or(0, |i| i + 1);
+    let mut doc: Vec<String> = content[..line_start]
+        .lines()
+        .rev()
+        .map(str::trim)
+        .take_while(|line| {
+            line.starts_with("--") && !line.starts_with("--!") && !line.starts_with("--:")
+        })
+        .map(|line| {
+            let line = &line[2..];
+            line.strip_prefix(' ').unwrap_or(line).to_string()
+        })
+        .collect();
+    doc.reverse();
+    doc
+}
+
 pub(crate) mod error {
     use miette::{Diagnostic, NamedSource, SourceSpan};
     use thiserror::Error as ThisError;
 
     #[derive(Debug, ThisError, Diagnostic)]
     #[error("Couldn't parse queries")]
+    #[diagnostic(code(cornucopia::E0001))]
     pub struct Error {
         #[source_code]
         pub src: NamedSource,
@@ -2,16 +2,17 @@ use std::rc::Rc;
 
 use heck::ToUpperCamelCase;
 use indexmap::{map::Entry, IndexMap};
-use postgres::Client;
 use postgres_types::{Kind, Type};
 
 use crate::{
-    parser::{Module, NullableIdent, Query, Span, TypeAnnotation},
+    metadata::{Metadata, StmtSource},
+    parser::{Module, NullableIdent, Query, QueryDataStruct, Span, TypeAnnotation},
     read_queries::ModuleInfo,
     type_registrar::CornucopiaType,
     type_registrar::TypeRegistrar,
     utils::escape_keyword,
     validation,
+    warning::{self, Warning},
 };
 
 use self::error::Error;
@@ -24,15 +25,23 @@ pub(crate) struct PreparedQuery {
     pub(crate) param: Option<(usize, Vec<usize>)>,
     pub(crate) row: Option<(usize, Vec<usize>)>,
     pub(crate) sql: String,
+    /// The SQL as written in the query file, with named parameters
+    pub(crate) source_sql: String,
+    pub(crate) doc: Vec<String>,
+    /// Set by the `deprecated` attribute, with its optional note
+    pub(crate) deprecated: Option<Option<String>>,
+    /// The other attributes, passed through to the generated code
+    pub(crate) attributes: Vec<(String, Option<String>)>,
 }
 
 /// A row or params field
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Eq)]
 pub struct PreparedField {
     pub(crate) name: String,
     pub(crate) ty: Rc<CornucopiaType>,
     pub(crate) is_nullable: bool,
     pub(crate) is_inner_nullable: bool, // Vec only
+    pub(crate) doc: Vec<String>,
 }
 
 impl PreparedField {
@@ -40,16 +49,28 @@ impl PreparedField {
         name: String,
         ty: Rc<CornucopiaType>,
         nullity: Option<&NullableIdent>,
+        doc: Vec<String>,
     ) -> Self {
         Self {
             name: escape_keyword(name),
             ty,
             is_nullable: nullity.map_or(false, |it| it.nullable),
             is_inner_nullable: nullity.map_or(false, |it| it.inner_nullable),
+            doc,
         }
     }
 }
 
+// Fields of rows shared by several queries may come from columns with different comments
+impl PartialEq for PreparedField {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.ty == other.ty
+            && self.is_nullable == other.is_nullable
+            && self.is_inner_nullable == other.is_inner_nullable
+    }
+}
+
 impl PreparedField {
     pub fn unwrapped_name(&self) -> String {
         self.own_struct()
@@ -65,12 +86,19 @@ pub(crate) struct PreparedItem {
     pub(crate) is_copy: bool,
     pub(crate) is_named: bool,
     pub(crate) is_ref: bool,
+    pub(crate) doc: Vec<String>,
 }
 
 impl PreparedItem {
-    pub fn new(name: Span<String>, fields: Vec<PreparedField>, is_implicit: bool) -> Self {
+    pub fn new(
+        name: Span<String>,
+        fields: Vec<PreparedField>,
+        is_implicit: bool,
+        doc: Vec<String>,
+    ) -> Self {
         Self {
             name,
+            doc,
             is_copy: fields.iter().all(|f| f.ty.is_copy()),
             is_ref: fields.iter().any(|f| f.ty.is_ref()),
             is_named: !is_implicit || fields.len() > 1,
@@ -86,6 +114,7 @@ pub(crate) struct PreparedType {
     pub(crate) content: PreparedContent,
     pub(crate) is_copy: bool,
     pub(crate) is_params: bool,
+    pub(crate) doc: Vec<String>,
 }
 
 #[derive(PartialEq, Eq, Debug, Clone)]
@@ -102,6 +131,7 @@ pub(crate) struct PreparedModule {
     pub(crate) queries: IndexMap<Span<String>, PreparedQuery>,
     pub(crate) params: IndexMap<Span<String>, PreparedItem>,
     pub(crate) rows: IndexMap<Span<String>, PreparedItem>,
+    pub(crate) warnings: Vec<Warning>,
 }
 
 #[derive(Debug, Clone)]
@@ -117,6 +147,7 @@ impl PreparedModule {
         name: Span<String>,
         fields: Vec<PreparedField>,
         is_implicit: bool,
+        doc: &[String],
     ) -> Result<(usize, Vec<usize>), Error> {
         assert!(!fields.is_empty());
         match map.entry(name.clone()) {
@@ -137,8 +168,13 @@ impl PreparedModule {
                 Ok((o.index(), indexes))
             }
             Entry::Vacant(v) => {
-                v.insert(PreparedItem::new(name.clone(), fields.clone(), is_implicit));
-                Self::add(info, map, name, fields, is_implicit)
+                v.insert(PreparedItem::new(
+                    name.clone(),
+                    fields.clone(),
+                    is_implicit,
+                    doc.to_vec(),
+                ));
+                Self::add(info, map, name, fields, is_implicit, doc)
             }
         }
     }
@@ -148,13 +184,14 @@ impl PreparedModule {
         name: Span<String>,
         fields: Vec<PreparedField>,
         is_implicit: bool,
+        doc: &[String],
     ) -> Result<(usize, Vec<usize>), Error> {
         let fuck = if fields.len() == 1 && is_implicit {
             name.map(|_| fields[0].unwrapped_name())
         } else {
             name
         };
-        Self::add(&self.info, &mut self.rows, fuck, fields, is_implicit)
+        Self::add(&self.info, &mut self.rows, fuck, fields, is_implicit, doc)
     }
 
     fn add_param(
@@ -162,31 +199,21 @@ impl PreparedModule {
         name: Span<String>,
         fields: Vec<PreparedField>,
         is_implicit: bool,
+        doc: &[String],
     ) -> Result<(usize, Vec<usize>), Error> {
-        Self::add(&self.info, &mut self.params, name, fields, is_implicit)
-    }
-
-    fn add_query(
-        &mut self,
-        name: Span<String>,
-        param_idx: Option<(usize, Vec<usize>)>,
-        row_idx: Option<(usize, Vec<usize>)>,
-        sql: String,
-    ) {
-        self.queries.insert(
-            name.clone(),
-            PreparedQuery {
-                name: name.value,
-                row: row_idx,
-                sql,
-                param: param_idx,
-            },
-        );
+        Self::add(&self.info, &mut self.params, name, fields, is_implicit, doc)
     }
 }
 
-/// Prepares all modules
-pub(crate) fn prepare(client: &mut Client, modules: Vec<Module>) -> Result<Preparation, Error> {
+/// Query attributes interpreted by cornucopia, the others are passed through to the generated code.
+const INTERPRETED_ATTRIBUTES: &[&str] = &["deprecated"];
+
+/// Prepares all modules. When some queries fail, the other queries are still prepared,
+/// so that all the errors are reported together.
+pub(crate) fn prepare(
+    source: &mut StmtSource,
+    modules: Vec<Module>,
+) -> Result<Preparation, Vec<Error>> {
     let mut registrar = TypeRegistrar::default();
     let mut tmp = Preparation {
         modules: Vec::new(),
@@ -197,15 +224,52 @@ pub(crate) fn prepare(client: &mut Client, modules: Vec<Module>) -> Result<Prepa
         .flat_map(|it| &it.types)
         .map(|ty| (*ty).clone())
         .collect();
+    // Declared types that no query uses, they may still be used by a custom type
+    let unused: Vec<_> = modules
+        .iter()
+        .enumerate()
+        .flat_map(|(i, module)| {
+            module
+                .types
+                .iter()
+                .filter(|ty| {
+                    !module.queries.iter().any(|query| {
+                        [&query.param, &query.row]
+                            .iter()
+                            .any(|it| !it.inlined() && it.name.as_ref() == Some(&ty.name))
+                    })
+                })
+                .map(move |ty| (i, ty.clone()))
+        })
+        .collect();
 
+    let mut errors = Vec::new();
     for module in modules {
-        tmp.modules
-            .push(prepare_module(client, module, &mut registrar)?);
+        match prepare_module(source, module, &mut registrar) {
+            Ok(module) => tmp.modules.push(module),
+            Err(module_errors) => errors.extend(module_errors),
+        }
+    }
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    for (i, ty) in unused {
+        if registrar
+            .types
+            .keys()
+            .all(|(_, name)| *name != ty.name.value)
+        {
+            let module = &mut tmp.modules[i];
+            module
+                .warnings
+                .push(warning::unused_type(&module.info, &ty));
+        }
     }
 
     // Prepare types grouped by schema
     for ((schema, name), ty) in &registrar.types {
-        if let Some(ty) = prepare_type(&registrar, name, ty, &declared) {
+        if let Some(ty) = prepare_type(&registrar, name, ty, &declared, source.metadata()) {
             match tmp.types.entry(schema.clone()) {
                 Entry::Occupied(mut entry) => {
                     entry.get_mut().push(ty);
@@ -223,12 +287,31 @@ fn normalize_rust_name(name: &str) -> String {
     name.replace(':', "_")
 }
 
+fn doc_lines(comment: &str) -> Vec<String> {
+    comment.lines().map(String::from).collect()
+}
+
+/// Documents a field with its comment, or with the comment of its type if it is a domain.
+fn field_doc(metadata: &Metadata, comment: Option<&str>, ty: &Type) -> Vec<String> {
+    let domain_comment = || match ty.kind() {
+        Kind::Domain(_) => metadata
+            .type_comments(ty)
+            .and_then(|it| it.comment.as_deref()),
+        _ => None,
+    };
+    comment
+        .or_else(domain_comment)
+        .map(doc_lines)
+        .unwrap_or_default()
+}
+
 /// Prepares database custom types
 fn prepare_type(
     registrar: &TypeRegistrar,
     name: &str,
     ty: &CornucopiaType,
     types: &[TypeAnnotation],
+    metadata: &Metadata,
 ) -> Option<PreparedType> {
     if let CornucopiaType::Custom {
         pg_ty,
@@ -238,10 +321,14 @@ fn prepare_type(
         ..
     } = ty
     {
-        let declared = types
-            .iter()
-            .find(|it| it.name.value == pg_ty.name())
-            .map_or(&[] as &[NullableIdent], |it| it.fields.as_slice());
+        let annotation = types.iter().find(|it| it.name.value == pg_ty.name());
+        let declared = annotation.map_or(&[] as &[NullableIdent], |it| it.fields.as_slice());
+        let comments = metadata.type_comments(pg_ty);
+        let member_comment = |name: &str| {
+            comments
+                .and_then(|it| it.members.get(name))
+                .map(String::as_str)
+        };
         let content = match pg_ty.kind() {
             Kind::Enum(variants) => {
                 PreparedContent::Enum(variants.clone().into_iter().map(escape_keyword).collect())
@@ -257,6 +344,7 @@ fn prepare_type(
                             field.name().to_string(),
                             registrar.ref_of(field.type_()),
                             nullity,
+                            field_doc(metadata, member_comment(field.name()), field.type_()),
                         )
                     })
                     .collect(),
@@ -269,6 +357,12 @@ fn prepare_type(
             content,
             is_copy: *is_copy,
             is_params: *is_params,
+            // Comments of the query files take precedence over those of the database
+            doc: annotation
+                .map(|it| it.doc.clone())
+                .filter(|doc| !doc.is_empty())
+                .or_else(|| comments.and_then(|it| it.comment.as_deref()).map(doc_lines))
+                .unwrap_or_default(),
         })
     } else {
         None
@@ -277,38 +371,56 @@ fn prepare_type(
 
 /// Prepares all queries in this module
 fn prepare_module(
-    client: &mut Client,
+    source: &mut StmtSource,
     module: Module,
     registrar: &mut TypeRegistrar,
-) -> Result<PreparedModule, Error> {
-    validation::validate_module(&module)?;
+) -> Result<PreparedModule, Vec<Error>> {
+    let errors = validation::validate_module(&module);
+    if !errors.is_empty() {
+        return Err(errors
+            .into_iter()
+            .map(|err| Error::from(Box::new(err)))
+            .collect());
+    }
 
     let mut tmp_prepared_module = PreparedModule {
         info: module.info.clone(),
         queries: IndexMap::new(),
         params: IndexMap::new(),
         rows: IndexMap::new(),
+        warnings: Vec::new(),
     };
 
+    let mut errors = Vec::new();
     for query in module.queries {
-        prepare_query(
-            client,
+        if let Err(err) = prepare_query(
+            source,
             &mut tmp_prepared_module,
             registrar,
             &module.types,
             query,
             &module.info,
-        )?;
+        ) {
+            errors.push(err);
+        }
     }
 
-    validation::validate_preparation(&tmp_prepared_module)?;
+    errors.extend(
+        validation::validate_preparation(&tmp_prepared_module)
+            .into_iter()
+            .map(|err| Error::from(Box::new(err))),
+    );
 
-    Ok(tmp_prepared_module)
+    if errors.is_empty() {
+        Ok(tmp_prepared_module)
+    } else {
+        Err(errors)
+    }
 }
 
 /// Prepares a query
 fn prepare_query(
-    client: &mut Client,
+    source: &mut StmtSource,
     module: &mut PreparedModule,
     registrar: &mut TypeRegistrar,
     types: &[TypeAnnotation],
@@ -319,18 +431,25 @@ fn prepare_query(
         row,
         sql_str,
         sql_span,
+        attributes,
+        doc,
     }: Query,
     module_info: &ModuleInfo,
 ) -> Result<(), Error> {
     // Prepare the statement
-    let stmt = client
+    let stmt = source
         .prepare(&sql_str)
-        .map_err(|e| Error::new_db_err(&e, module_info, &sql_span, &name))?;
+        .map_err(|e| Error::new_db_err(&e, module_info, &sql_span, &name))?
+        .ok_or_else(|| Error::MissingMetadata {
+            name: name.value.clone(),
+            src: module_info.into(),
+            query: sql_span,
+        })?;
 
     let (nullable_params_fields, params_name) = param.name_and_fields(types, &name, Some("Params"));
     let (nullable_row_fields, row_name) = row.name_and_fields(types, &name, None);
     let params_fields = {
-        let stmt_params = stmt.params();
+        let stmt_params = &stmt.params;
         let params = bind_params
             .iter()
             .zip(stmt_params)
@@ -338,6 +457,9 @@ fn prepare_query(
             .collect::<Vec<(Span<String>, Type)>>();
         // Check for param declaration on simple query
         validation::param_on_simple_query(&module.info, &name, &sql_span, &param, &params)?;
+        module
+            .warnings
+            .extend(warning::check_sql(module_info, &sql_span, &params));
         for nullable_col in nullable_params_fields {
             // If none of the row's columns match the nullable column
             validation::nullable_param_name(&module.info, nullable_col, &params)
@@ -350,58 +472,127 @@ fn prepare_query(
                 .iter()
                 .find(|x| x.name.value == col_name.value);
             // Register type
+            let ty = registrar
+                .register(&col_name.value, &col_ty, &name, module_info)?
+                .clone();
+            source
+                .record_type_comments(&col_ty)
+                .map_err(|e| Error::new_db_err(&e, module_info, &sql_span, &name))?;
             param_fields.push(PreparedField::new(
                 col_name.value.clone(),
-                registrar
-                    .register(&col_name.value, &col_ty, &name, module_info)?
-                    .clone(),
+                ty,
                 nullity,
+                Vec::new(),
             ));
         }
         param_fields
     };
 
     let row_fields = {
-        let stmt_cols = stmt.columns();
+        let stmt_cols = &stmt.columns;
         // Check for row declaration on execute
         validation::row_on_execute(&module.info, &name, &sql_span, &row, stmt_cols)?;
         // Check for duplicate names
         validation::duplicate_sql_col_name(&module.info, &name, stmt_cols).map_err(Error::from)?;
+        // Columns of an outer join may be null even if declared `NOT NULL`
+        let outer_join = warning::has_outer_join(&module.info, &sql_span);
         for nullable_col in nullable_row_fields {
             // If none of the row's columns match the nullable column
             validation::nullable_column_name(&module.info, nullable_col, stmt_cols)
                 .map_err(Error::from)?;
+            // Named types used by several queries are only reported once
+            let reported = module.warnings.iter().any(|it| {
+                matches!(it, Warning::NullableNotNull { pos, .. } if *pos == nullable_col.name.span)
+            });
+            if !reported && !outer_join {
+                module.warnings.extend(warning::nullable_not_null(
+                    &module.info,
+                    nullable_col,
+                    stmt_cols,
+                ));
+            }
         }
 
         let mut row_fields = Vec::new();
-        for (col_name, col_ty) in stmt_cols.iter().map(|c| (c.name().to_owned(), c.type_())) {
+        for col in stmt_cols {
+            let col_name = col.name();
             let nullity = nullable_row_fields
                 .iter()
                 .find(|x| x.name.value == col_name);
             // Register type
             let ty = registrar
-                .register(&col_name, col_ty, &name, module_info)?
+                .register(col_name, col.type_(), &name, module_info)?
                 .clone();
+            source
+                .record_type_comments(col.type_())
+                .map_err(|e| Error::new_db_err(&e, module_info, &sql_span, &name))?;
             row_fields.push(PreparedField::new(
-                normalize_rust_name(&col_name),
+                normalize_rust_name(col_name),
                 ty,
                 nullity,
+                field_doc(source.metadata(), col.comment(), col.type_()),
             ));
         }
         row_fields
     };
 
+    // Structs declared with `--:` are documented by their own comments, and the structs
+    // implicit to this query by its comments. Other structs may be shared by several queries.
+    let struct_doc = |data: &QueryDataStruct| match data
+        .name
+        .as_ref()
+        .and_then(|name| types.iter().find(|it| it.name == *name))
+    {
+        Some(ty) => ty.doc.as_slice(),
+        None if data.is_implicit() => doc.as_slice(),
+        None => &[],
+    };
     let row_idx = if row_fields.is_empty() {
         None
     } else {
-        Some(module.add_row(row_name, row_fields, row.is_implicit())?)
+        Some(module.add_row(row_name, row_fields, row.is_implicit(), struct_doc(&row))?)
     };
     let param_idx = if params_fields.is_empty() {
         None
     } else {
-        Some(module.add_param(params_name, params_fields, param.is_implicit())?)
+        Some(module.add_param(
+            params_name,
+            params_fields,
+            param.is_implicit(),
+            struct_doc(&param),
+        )?)
     };
-    module.add_query(name.clone(), param_idx, row_idx, sql_str);
+    // Spans count chars, not bytes
+    let source_sql: String = module_info
+        .content
+        .chars()
+        .skip(sql_span.offset())
+        .take(sql_span.len())
+        .collect();
+    module.warnings.extend(
+        attributes
+            .iter()
+            .filter(|it| !INTERPRETED_ATTRIBUTES.contains(&it.name.value.as_str()))
+            .map(|it| warning::unknown_attribute(module_info, &it.name, INTERPRETED_ATTRIBUTES)),
+    );
+    let (deprecated, attributes): (Vec<_>, Vec<_>) = attributes
+        .into_iter()
+        .map(|it| (it.name.value, it.value.map(|value| value.value)))
+        .partition(|(name, _)| name == "deprecated");
+    let deprecated = deprecated.into_iter().next().map(|(_, note)| note);
+    module.queries.insert(
+        name.clone(),
+        PreparedQuery {
+            name: name.value,
+            param: param_idx,
+            row: row_idx,
+            sql: sql_str,
+            source_sql: source_sql.trim().to_string(),
+            doc,
+            deprecated,
+            attributes,
+        },
+    );
 
     Ok(())
 }
@@ -418,6 +609,7 @@ pub(crate) mod error {
     #[derive(Debug, ThisError, Diagnostic)]
     pub enum Error {
         #[error("Couldn't prepare query: {msg}")]
+        #[diagnostic(code(cornucopia::E0013))]
         Db {
             msg: String,
             #[help]
@@ -427,6 +619,18 @@ pub(crate) mod error {
             #[label("error occurs near this location")]
             err_span: Option<SourceSpan>,
         },
+        #[error("Couldn't find query `{name}` in offline metadata")]
+        #[diagnostic(
+            code(cornucopia::E0014),
+            help("regenerate the metadata file against a database")
+        )]
+        MissingMetadata {
+            name: String,
+            #[source_code]
+            src: NamedSource,
+            #[label("this query changed since the metadata was generated")]
+            query: SourceSpan,
+        },
         #[error(transparent)]
         #[diagnostic(transparent)]
         PostgresType(#[from] PostgresTypeError),
@@ -1,10 +1,19 @@
+use std::path::{Path, PathBuf};
+
 use miette::NamedSource;
 
+use crate::utils::KEYWORD;
+
 use self::error::Error;
 
+/// Name of the generated module holding the Postgres types.
+const TYPES_MODULE: &str = "types";
+
 #[derive(Debug, Clone)]
 pub(crate) struct ModuleInfo {
     pub(crate) path: String,
+    /// Subdirectories containing this module, mirrored as parent modules
+    pub(crate) parents: Vec<String>,
     pub(crate) name: String,
     pub(crate) content: String,
 }
@@ -21,51 +30,134 @@ impl From<&ModuleInfo> for NamedSource {
     }
 }
 
-/// Reads queries in the directory. Only .sql files are considered.
+/// Reads queries in the directory and its subdirectories. Only .sql files are considered.
 ///
 /// # Error
 /// Returns an error if `dir_path` does not point to a valid directory or if a query file cannot be parsed.
 pub(crate) fn read_query_modules(dir_path: &str) -> Result<Vec<ModuleInfo>, Error> {
     let mut modules_info = Vec::new();
-    for entry_result in std::fs::read_dir(dir_path).map_err(|err| Error {
+    read_dir(Path::new(dir_path), &[], &mut modules_info)?;
+    // Sort module for consistent codegen
+    modules_info.sort_by(|a, b| (&a.parents, &a.name).cmp(&(&b.parents, &b.name)));
+    check_types_clash(Path::new(dir_path), &modules_info)?;
+    Ok(modules_info)
+}
+
+/// Once queries are nested in directories, the generated modules import `types`,
+/// so no query file nor directory can generate a module with that name.
+fn check_types_clash(dir_path: &Path, modules_info: &[ModuleInfo]) -> Result<(), Error> {
+    if modules_info.iter().all(|module| module.parents.is_empty()) {
+        return Ok(());
+    }
+    for module in modules_info {
+        let clash = if module.name == TYPES_MODULE {
+            Some(PathBuf::from(&module.path))
+        } else {
+            module
+                .parents
+                .iter()
+                .position(|parent| parent == TYPES_MODULE)
+                .map(|i| {
+                    module.parents[..=i]
+                        .iter()
+                        .fold(dir_path.to_path_buf(), |path, parent| path.join(parent))
+                })
+        };
+        if let Some(path) = clash {
+            return Err(Error::InvalidModuleName {
+                name: String::from(TYPES_MODULE),
+                path: path.to_string_lossy().into_owned(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Reads queries in `dir_path`, recursing into subdirectories.
+fn read_dir(
+    dir_path: &Path,
+    parents: &[String],
+    modules_info: &mut Vec<ModuleInfo>,
+) -> Result<(), Error> {
+    let dir_err = |err| Error::Io {
         err,
-        path: String::from(dir_path),
-    })? {
+        path: dir_path.to_string_lossy().into_owned(),
+    };
+    // Subdirectories containing queries, and query files, by module name
+    let mut dirs = Vec::new();
+    let mut files = Vec::new();
+    for entry_result in std::fs::read_dir(dir_path).map_err(dir_err)? {
         // Directory entry
-        let entry = entry_result.map_err(|err| Error {
-            err,
-            path: dir_path.to_owned(),
-        })?;
+        let entry = entry_result.map_err(dir_err)?;
         let path_buf = entry.path();
 
-        // Check we're dealing with a .sql file
-        if path_buf
+        if entry.file_type().map_err(dir_err)?.is_dir() {
+            let dir_name = entry.file_name().to_string_lossy().into_owned();
+            let parents = [parents, &[dir_name.clone()]].concat();
+            let len = modules_info.len();
+            read_dir(&path_buf, &parents, modules_info)?;
+            // Directories without queries generate no module
+            if modules_info.len() > len {
+                module_name(&path_buf, entry.file_name().to_str())?;
+                check_module_name(&path_buf, &dir_name)?;
+                dirs.push((dir_name, path_buf));
+            }
+        } else if path_buf
             .extension()
             .map(|extension| extension == "sql")
             .unwrap_or_default()
         {
-            let module_name = path_buf
-                .file_stem()
-                .expect("is a file")
-                .to_str()
-                .expect("file name is valid utf8")
-                .to_string();
-
-            let file_contents = std::fs::read_to_string(&path_buf).map_err(|err| Error {
-                err,
-                path: dir_path.to_owned(),
-            })?;
+            // Check we're dealing with a .sql file
+            let module_name =
+                module_name(&path_buf, path_buf.file_stem().and_then(|it| it.to_str()))?;
+            check_module_name(&path_buf, &module_name)?;
+
+            let file_contents = std::fs::read_to_string(&path_buf).map_err(dir_err)?;
 
+            files.push((module_name.clone(), path_buf.clone()));
             modules_info.push(ModuleInfo {
                 path: String::from(path_buf.to_string_lossy()),
+                parents: parents.to_vec(),
                 name: module_name,
                 content: file_contents,
             });
         }
     }
-    // Sort module for consistent codegen
-    modules_info.sort_by(|a, b| a.name.cmp(&b.name));
-    Ok(modules_info)
+    // A query file and a subdirectory can't both generate the same module
+    for (name, file) in &files {
+        if let Some((_, dir)) = dirs.iter().find(|(dir_name, _)| dir_name == name) {
+            return Err(Error::ModuleCollision {
+                name: name.clone(),
+                file: file.to_string_lossy().into_owned(),
+                dir: dir.to_string_lossy().into_owned(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Name of the module generated from `path`, which must be valid UTF-8.
+fn module_name(path: &Path, name: Option<&str>) -> Result<String, Error> {
+    name.map(String::from).ok_or_else(|| Error::InvalidUtf8 {
+        path: path.to_string_lossy().into_owned(),
+    })
+}
+
+/// Checks that `name` can be used as the name of a generated module.
+fn check_module_name(path: &Path, name: &str) -> Result<(), Error> {
+    let mut chars = name.chars();
+    let is_ident = chars
+        .next()
+        .map_or(false, |c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if is_ident && KEYWORD.binary_search(&name).is_err() {
+        Ok(())
+    } else {
+        Err(Error::InvalidModuleName {
+            name: name.to_string(),
+            path: path.to_string_lossy().into_owned(),
+        })
+    }
 }
 
 pub(crate) mod error {
@@ -73,9 +165,23 @@ pub(crate) mod error {
     use thiserror::Error as ThisError;
 
     #[derive(Debug, ThisError, Diagnostic)]
-    #[error("[{path}] : {err:#}")]
-    pub struct Error {
-        pub(crate) err: std::io::Error,
-        pub(crate) path: String,
+    pub enum Error {
+        #[error("[{path}] : {err:#}")]
+        Io { err: std::io::Error, path: String },
+        #[error("[{path}] : the name is not valid UTF-8")]
+        #[diagnostic(help("rename it, as its name is used as a module name"))]
+        InvalidUtf8 { path: String },
+        #[error("[{path}] : `{name}` is not a valid module name")]
+        #[diagnostic(help(
+            "rename it with ASCII letters, digits and underscores, avoiding rust keywords, and `types` when queries are in directories"
+        ))]
+        InvalidModuleName { name: String, path: String },
+        #[error("[{file}] and [{dir}] both generate the module `{name}`")]
+        #[diagnostic(help("rename the query file or the directory"))]
+        ModuleCollision {
+            name: String,
+            file: String,
+            dir: String,
+        },
     }
 }
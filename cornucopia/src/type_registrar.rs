@@ -441,7 +441,9 @@ pub(crate) mod error {
     #[derive(Debug, ThisError, Diagnostic)]
     #[error("Couldn't register SQL type.")]
     pub enum Error {
+        #[diagnostic(code(cornucopia::E0015))]
         Db(#[from] postgres::Error),
+        #[diagnostic(code(cornucopia::E0016))]
         UnsupportedPostgresType {
             #[source_code]
             src: NamedSource,
@@ -1,7 +1,8 @@
 use std::collections::BTreeMap;
 
 use crate::{
-    parser::{Module, NullableIdent, Query, QueryDataStruct, Span, TypeAnnotation},
+    metadata::Column,
+    parser::{Attribute, Module, NullableIdent, Query, QueryDataStruct, Span, TypeAnnotation},
     prepare_queries::{PreparedField, PreparedModule},
     read_queries::ModuleInfo,
     utils::{find_duplicate, unescape_keyword, STRICT_KEYWORD},
@@ -10,7 +11,6 @@ use crate::{
 use error::Error;
 use heck::ToUpperCamelCase;
 use miette::SourceSpan;
-use postgres::Column;
 use postgres_types::Type;
 
 pub(crate) fn duplicate_nullable_ident(
@@ -27,6 +27,20 @@ pub(crate) fn duplicate_nullable_ident(
     })
 }
 
+pub(crate) fn duplicate_attribute(
+    info: &ModuleInfo,
+    attributes: &[Attribute],
+) -> Result<(), Box<Error>> {
+    find_duplicate(attributes, |a, b| a.name == b.name).map_or(Ok(()), |(first, second)| {
+        Err(Box::new(Error::DuplicateAttribute {
+            src: info.into(),
+            name: first.name.value.clone(),
+            first: first.name.span,
+            second: second.name.span,
+        }))
+    })
+}
+
 pub(crate) fn duplicate_sql_col_name(
     info: &ModuleInfo,
     query_name: &Span<String>,
@@ -267,7 +281,14 @@ pub(crate) fn named_struct_field(
     Ok(())
 }
 
-pub(crate) fn validate_preparation(module: &PreparedModule) -> Result<(), Box<Error>> {
+/// Checks the generated names of a prepared module, returning all the errors found.
+pub(crate) fn validate_preparation(module: &PreparedModule) -> Vec<Error> {
+    let mut errors = Vec::new();
+    let mut check = |result: Result<(), Box<Error>>| {
+        if let Err(err) = result {
+            errors.push(*err);
+        }
+    };
     // Check generated name clash
     let mut name_registrar = BTreeMap::new();
 
@@ -279,80 +300,106 @@ pub(crate) fn validate_preparation(module: &PreparedModule) -> Result<(), Box<Er
             } else {
                 ((span, ty), prev)
             };
-            Err(Error::DuplicateName {
+            Err(Box::new(Error::DuplicateName {
                 src: (&module.info).into(),
                 name,
                 first: first.0,
                 first_ty: first.1,
                 second: second.0,
                 second_ty: second.1,
-            })
+            }))
         } else {
             Ok(())
         }
     };
 
     for (origin, query) in &module.queries {
-        reserved_type_keyword(&module.info, origin)?;
-        check_name(
+        check(reserved_type_keyword(&module.info, origin));
+        check(check_name(
             format!("{}Stmt", query.name.to_upper_camel_case()),
             origin.span,
             "statement",
-        )?;
+        ));
     }
     for (origin, row) in &module.rows {
-        reserved_type_keyword(&module.info, origin)?;
+        check(reserved_type_keyword(&module.info, origin));
         if row.is_named {
-            check_name(row.name.value.clone(), origin.span, "row")?;
+            check(check_name(row.name.value.clone(), origin.span, "row"));
             for field in &row.fields {
-                reserved_name_keyword(&module.info, &field.name, &origin.span, "row")?;
+                check(reserved_name_keyword(
+                    &module.info,
+                    &field.name,
+                    &origin.span,
+                    "row",
+                ));
             }
 
             if !row.is_copy {
-                check_name(format!("{}Borrowed", row.name), origin.span, "borrowed row")?;
+                check(check_name(
+                    format!("{}Borrowed", row.name),
+                    origin.span,
+                    "borrowed row",
+                ));
             };
         }
-        check_name(format!("{}Query", row.name), origin.span, "query")?;
+        check(check_name(
+            format!("{}Query", row.name),
+            origin.span,
+            "query",
+        ));
     }
     for (origin, params) in &module.params {
-        reserved_type_keyword(&module.info, origin)?;
+        check(reserved_type_keyword(&module.info, origin));
         if params.is_named {
-            check_name(params.name.value.clone(), origin.span, "params")?;
+            check(check_name(params.name.value.clone(), origin.span, "params"));
             for field in &params.fields {
-                reserved_name_keyword(&module.info, &field.name, &origin.span, "param")?;
+                check(reserved_name_keyword(
+                    &module.info,
+                    &field.name,
+                    &origin.span,
+                    "param",
+                ));
             }
         }
     }
-    Ok(())
+    errors
 }
 
+/// Checks the annotations of a parsed module, returning all the errors found.
 pub(crate) fn validate_module(
     Module {
         info,
         types,
         queries,
     }: &Module,
-) -> Result<(), Box<Error>> {
-    query_name_already_used(info, queries)?;
-    named_type_already_used(info, types)?;
+) -> Vec<Error> {
+    let mut errors = Vec::new();
+    let mut check = |result: Result<(), Box<Error>>| {
+        if let Err(err) = result {
+            errors.push(*err);
+        }
+    };
+    check(query_name_already_used(info, queries));
+    check(named_type_already_used(info, types));
     for ty in types {
-        duplicate_nullable_ident(info, &ty.fields)?;
+        check(duplicate_nullable_ident(info, &ty.fields));
     }
     for query in queries {
+        check(duplicate_attribute(info, &query.attributes));
         for (it, ty) in [(&query.param, "param"), (&query.row, "row")] {
             if let Some(idents) = &it.idents {
-                duplicate_nullable_ident(info, idents)?;
+                check(duplicate_nullable_ident(info, idents));
             };
             if let Some(name) = &it.name {
                 if it.inlined() {
-                    inline_conflict_declared(info, name, types, ty)?;
+                    check(inline_conflict_declared(info, name, types, ty));
                 } else {
-                    reference_unknown_type(info, name, types, ty)?;
+                    check(reference_unknown_type(info, name, types, ty));
                 }
             }
         }
     }
-    Ok(())
+    errors
 }
 
 pub mod error {
@@ -364,7 +411,10 @@ pub mod error {
     #[derive(Debug, ThisError, Diagnostic)]
     pub enum Error {
         #[error("column `{name}` appear multiple time")]
-        #[diagnostic(help("disambiguate column names in your SQL using an `AS` clause"))]
+        #[diagnostic(
+            code(cornucopia::E0002),
+            help("disambiguate column names in your SQL using an `AS` clause")
+        )]
         DuplicateSqlColName {
             #[source_code]
             src: NamedSource,
@@ -373,7 +423,7 @@ pub mod error {
             pos: SourceSpan,
         },
         #[error("the field `{name}` is declared null multiple time")]
-        #[diagnostic(help("remove one of the two declaration"))]
+        #[diagnostic(code(cornucopia::E0003), help("remove one of the two declaration"))]
         DuplicateFieldNullity {
             #[source_code]
             src: NamedSource,
@@ -384,7 +434,7 @@ pub mod error {
             second: SourceSpan,
         },
         #[error("the {ty} `{name}` is defined multiple time")]
-        #[diagnostic(help("use a different name for one of those"))]
+        #[diagnostic(code(cornucopia::E0004), help("use a different name for one of those"))]
         DuplicateType {
             #[source_code]
             src: NamedSource,
@@ -396,7 +446,10 @@ pub mod error {
             second: SourceSpan,
         },
         #[error("reference to an unknown named {ty} `{name}`")]
-        #[diagnostic(help("declare an inline named type using `()`: {name}()"))]
+        #[diagnostic(
+            code(cornucopia::E0005),
+            help("declare an inline named type using `()`: {name}()")
+        )]
         UnknownNamedType {
             #[source_code]
             src: NamedSource,
@@ -406,7 +459,7 @@ pub mod error {
             pos: SourceSpan,
         },
         #[error("unknown field")]
-        #[diagnostic(help("use one of those names: {known}"))]
+        #[diagnostic(code(cornucopia::E0006), help("use one of those names: {known}"))]
         UnknownFieldName {
             #[source_code]
             src: NamedSource,
@@ -415,7 +468,10 @@ pub mod error {
             known: String,
         },
         #[error("named type `{name}` as conflicting usage")]
-        #[diagnostic(help("use a different named type for each query"))]
+        #[diagnostic(
+            code(cornucopia::E0007),
+            help("use a different named type for each query")
+        )]
         IncompatibleNamedType {
             #[source_code]
             src: NamedSource,
@@ -428,7 +484,7 @@ pub mod error {
             second: SourceSpan,
         },
         #[error("the query `{name}` declare a row but return nothing")]
-        #[diagnostic(help("remove row declaration"))]
+        #[diagnostic(code(cornucopia::E0008), help("remove row declaration"))]
         RowOnExecute {
             #[source_code]
             src: NamedSource,
@@ -439,7 +495,7 @@ pub mod error {
             query: SourceSpan,
         },
         #[error("the query `{name}` declares a parameter but has no binding")]
-        #[diagnostic(help("remove parameter declaration"))]
+        #[diagnostic(code(cornucopia::E0009), help("remove parameter declaration"))]
         ParamsOnSimpleQuery {
             #[source_code]
             src: NamedSource,
@@ -450,7 +506,7 @@ pub mod error {
             query: SourceSpan,
         },
         #[error("`{name}` is used multiple time")]
-        #[diagnostic(help("use a different name for one of those"))]
+        #[diagnostic(code(cornucopia::E0010), help("use a different name for one of those"))]
         DuplicateName {
             #[source_code]
             src: NamedSource,
@@ -463,7 +519,7 @@ pub mod error {
             second: SourceSpan,
         },
         #[error("`{name}` is a reserved rust keyword that cannot be escaped")]
-        #[diagnostic(help("use a different name"))]
+        #[diagnostic(code(cornucopia::E0011), help("use a different name"))]
         TypeRustKeyword {
             #[source_code]
             src: NamedSource,
@@ -472,7 +528,7 @@ pub mod error {
             pos: SourceSpan,
         },
         #[error("`{name}` is a reserved rust keyword that cannot be escaped")]
-        #[diagnostic(help("use a different name"))]
+        #[diagnostic(code(cornucopia::E0012), help("use a different name"))]
         NameRustKeyword {
             #[source_code]
             src: NamedSource,
@@ -481,5 +537,16 @@ pub mod error {
             #[label("from {ty} declared here")]
             pos: SourceSpan,
         },
+        #[error("the attribute `{name}` is declared multiple time")]
+        #[diagnostic(code(cornucopia::E0017), help("remove one of the two declarations"))]
+        DuplicateAttribute {
+            #[source_code]
+            src: NamedSource,
+            name: String,
+            #[label("previous declaration")]
+            first: SourceSpan,
+            #[label("redeclared here")]
+            second: SourceSpan,
+        },
     }
 }
@@ -0,0 +1,351 @@
+use std::{ops::Range, sync::Arc};
+
+use miette::{Diagnostic, NamedSource, SourceSpan};
+use postgres_types::Type;
+use thiserror::Error as ThisError;
+
+use crate::{
+    error::render_report,
+    message_format::render_json,
+    metadata::Column,
+    parser::{NullableIdent, Span, TypeAnnotation},
+    read_queries::ModuleInfo,
+};
+
+/// Checks the SQL of a query for `*` selecting all columns, and for
+/// parameters inferred as `text` because they are compared to an untyped literal.
+pub(crate) fn check_sql(
+    info: &ModuleInfo,
+    sql_span: &SourceSpan,
+    params: &[(Span<String>, Type)],
+) -> Vec<Warning> {
+    let start = sql_span.offset();
+    // Spans are not guaranteed to fall on char boundaries, skip those checks if they don't
+    let sql = match info.content.get(start..start + sql_span.len()) {
+        Some(sql) => sql,
+        None => return Vec::new(),
+    };
+    let tokens = tokenize(sql);
+    let pos = |range: &Range<usize>| SourceSpan::from(start + range.start..start + range.end);
+    let mut warnings = Vec::new();
+
+    for (i, (token, range)) in tokens.iter().enumerate() {
+        if *token != Token::Op("*") {
+            continue;
+        }
+        let selects_all = match i.checked_sub(1).map(|prev| &tokens[prev].0) {
+            Some(Token::Word(word)) => ["select", "distinct", "all"]
+                .iter()
+                .any(|keyword| word.eq_ignore_ascii_case(keyword)),
+            Some(Token::Punct(c)) => *c == '.' || *c == ',',
+            _ => false,
+        };
+        if selects_all {
+            warnings.push(Warning::SelectStar {
+                src: Arc::new(info.into()),
+                pos: pos(range),
+            });
+        }
+    }
+
+    for (name, ty) in params {
+        if *ty != Type::TEXT {
+            continue;
+        }
+        let untyped = tokens.iter().enumerate().find(|(i, (token, _))| {
+            *token == Token::Bind(&name.value) && compared_to_untyped_literal(&tokens, *i)
+        });
+        if let Some((_, (_, range))) = untyped {
+            warnings.push(Warning::UntypedParam {
+                src: Arc::new(info.into()),
+                name: name.value.clone(),
+                pos: pos(range),
+            });
+        }
+    }
+    warnings
+}
+
+/// Whether the bind at index `i` is compared or concatenated to an untyped string literal,
+/// without any cast telling Postgres which type to use.
+fn compared_to_untyped_literal(tokens: &[(Token<'_>, Range<usize>)], i: usize) -> bool {
+    let at = |i: Option<usize>| i.and_then(|i| tokens.get(i)).map(|(token, _)| token);
+    let is_comparison = |token: Option<&Token>| match token {
+        Some(Token::Op(op)) => ["=", "<>", "!=", "<", ">", "<=", ">=", "||"].contains(op),
+        Some(Token::Word(word)) => {
+            word.eq_ignore_ascii_case("like") || word.eq_ignore_ascii_case("ilike")
+        }
+        _ => false,
+    };
+    let cast = Some(&Token::Op("::"));
+    if at(Some(i + 1)) == cast {
+        return false;
+    }
+    let before =
+        is_comparison(at(i.checked_sub(1))) && at(i.checked_sub(2)) == Some(&Token::Literal);
+    let after = is_comparison(at(Some(i + 1)))
+        && at(Some(i + 2)) == Some(&Token::Literal)
+        && at(Some(i + 3)) != cast;
+    before || after
+}
+
+/// Checks that a nullable field of a row is not a table column declared `NOT NULL`.
+pub(crate) fn nullable_not_null(
+    info: &ModuleInfo,
+    nullable_col: &NullableIdent,
+    stmt_cols: &[Column],
+) -> Option<Warning> {
+    let not_null = stmt_cols
+        .iter()
+        .any(|col| col.name() == nullable_col.name.value && col.not_null());
+    (nullable_col.nullable && not_null).then(|| Warning::NullableNotNull {
+        src: Arc::new(info.into()),
+        name: nullable_col.name.value.clone(),
+        pos: nullable_col.name.span,
+    })
+}
+
+/// Whether the SQL of a query has an outer join, whose columns may be null-extended even
+/// if their table column is declared `NOT NULL`.
+pub(crate) fn has_outer_join(info: &ModuleInfo, sql_span: &SourceSpan) -> bool {
+    let start = sql_span.offset();
+    let sql = match info.content.get(start..start + sql_span.len()) {
+        Some(sql) => sql,
+        // Assume the worst, so that no false warning is reported
+        None => return true,
+    };
+    let tokens = tokenize(sql);
+    let is_word = |i: usize, keyword: &str| matches!(tokens.get(i), Some((Token::Word(word), _)) if word.eq_ignore_ascii_case(keyword));
+    (0..tokens.len()).any(|i| {
+        ["left", "right", "full"]
+            .iter()
+            .any(|kind| is_word(i, kind))
+            && (is_word(i + 1, "join") || (is_word(i + 1, "outer") && is_word(i + 2, "join")))
+    })
+}
+
+/// Reports a type declared with `--:` but neither used by a query, nor matching a custom type.
+pub(crate) fn unused_type(info: &ModuleInfo, ty: &TypeAnnotation) -> Warning {
+    Warning::UnusedType {
+        src: Arc::new(info.into()),
+        name: ty.name.value.clone(),
+        pos: ty.name.span,
+    }
+}
+
+/// Reports an attribute that cornucopia does not interpret, which may be a misspelling.
+pub(crate) fn unknown_attribute(info: &ModuleInfo, name: &Span<String>, known: &[&str]) -> Warning {
+    let known = known
+        .iter()
+        .map(|it| format!("`{it}`"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    Warning::UnknownAttribute {
+        src: Arc::new(info.into()),
+        name: name.value.clone(),
+        known,
+        pos: name.span,
+    }
+}
+
+/// SQL tokens, as far as warnings are concerned.
+#[derive(Debug, PartialEq, Eq)]
+enum Token<'a> {
+    /// A keyword, identifier or number
+    Word(&'a str),
+    /// A string constant, whose type is unknown to Postgres unless cast
+    Literal,
+    /// A named parameter, like `:id`
+    Bind(&'a str),
+    Op(&'a str),
+    Punct(char),
+}
+
+/// Splits `sql` into tokens, skipping comments and whitespaces.
+fn tokenize(sql: &str) -> Vec<(Token<'_>, Range<usize>)> {
+    const OPERATOR_CHARS: &[u8] = b"+-*/<>=~!@#%^&|`?";
+    let bytes = sql.as_bytes();
+    let is_word = |b: u8| b.is_ascii_alphanumeric() || b == b'_' || b == b'$' || !b.is_ascii();
+    let skip_while = |from: usize, f: &dyn Fn(u8) -> bool| {
+        from + bytes[from..].iter().take_while(|b| f(**b)).count()
+    };
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        let start = i;
+        let token = match bytes[i] {
+            b if b.is_ascii_whitespace() => {
+                i += 1;
+                continue;
+            }
+            b'-' if bytes.get(i + 1) == Some(&b'-') => {
+                i = sql[i..].find('\n').map_or(bytes.len(), |n| i + n);
+                continue;
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                let mut depth = 0;
+                while i < bytes.len() {
+                    if bytes[i..].starts_with(b"/*") {
+                        depth += 1;
+                        i += 2;
+                    } else if bytes[i..].starts_with(b"*/") {
+                        depth -= 1;
+                        i += 2;
+                        if depth == 0 {
+                            break;
+                        }
+                    } else {
+                        i += 1;
+                    }
+                }
+                continue;
+            }
+            b'\'' => {
+                i = end_of_string(bytes, i + 1, false);
+                Token::Literal
+            }
+            b'e' | b'E' if bytes.get(i + 1) == Some(&b'\'') => {
+                i = end_of_string(bytes, i + 2, true);
+                Token::Literal
+            }
+            b'"' => {
+                i = sql[i + 1..].find('"').map_or(bytes.len(), |n| i + n + 2);
+                Token::Word(&sql[start..i])
+            }
+            b'$' => {
+                let tag_end = skip_while(i + 1, &|b| b.is_ascii_alphanumeric() || b == b'_');
+                let is_tag = bytes.get(tag_end) == Some(&b'$')
+                    && !matches!(bytes.get(i + 1), Some(b) if b.is_ascii_digit());
+                if is_tag {
+                    let tag = &sql[i..=tag_end];
+                    i = sql[tag_end + 1..]
+                        .find(tag)
+                        .map_or(bytes.len(), |n| tag_end + 1 + n + tag.len());
+                    Token::Literal
+                } else {
+                    // Positional parameter, like `$1`
+                    i = tag_end;
+                    Token::Word(&sql[start..i])
+                }
+            }
+            b':' if bytes.get(i + 1) == Some(&b':') => {
+                i += 2;
+                Token::Op("::")
+            }
+            b':' if matches!(bytes.get(i + 1), Some(b) if b.is_ascii_alphabetic()) => {
+                i = skip_while(i + 1, &|b| b.is_ascii_alphanumeric() || b == b'_');
+                Token::Bind(&sql[start + 1..i])
+            }
+            b if is_word(b) => {
+                i = skip_while(i, &is_word);
+                Token::Word(&sql[start..i])
+            }
+            b if OPERATOR_CHARS.contains(&b) => {
+                i = skip_while(i, &|b| OPERATOR_CHARS.contains(&b));
+                Token::Op(&sql[start..i])
+            }
+            b => {
+                i += 1;
+                Token::Punct(b as char)
+            }
+        };
+        tokens.push((token, start..i));
+    }
+    tokens
+}
+
+/// Returns the end of a string constant whose content starts at `i`.
+fn end_of_string(bytes: &[u8], mut i: usize, backslash_escapes: bool) -> usize {
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' if backslash_escapes => i += 2,
+            b'\'' if bytes.get(i + 1) == Some(&b'\'') => i += 2,
+            b'\'' => return i + 1,
+            _ => i += 1,
+        }
+    }
+    bytes.len()
+}
+
+/// Non-fatal diagnostics about queries, reported without failing code generation.
+#[derive(Debug, Clone, ThisError, Diagnostic)]
+pub enum Warning {
+    #[error("the query selects all columns with `*`")]
+    #[diagnostic(
+        code(cornucopia::W0001),
+        severity(Warning),
+        help("list the columns explicitly, so that schema changes don't silently change the generated row")
+    )]
+    SelectStar {
+        #[source_code]
+        src: Arc<NamedSource>,
+        #[label("expands to all columns")]
+        pos: SourceSpan,
+    },
+    #[error("the field `{name}` is declared nullable, but its column is `NOT NULL`")]
+    #[diagnostic(
+        code(cornucopia::W0002),
+        severity(Warning),
+        help("remove the `?`, as the column can never be null")
+    )]
+    NullableNotNull {
+        #[source_code]
+        src: Arc<NamedSource>,
+        name: String,
+        #[label("declared nullable here")]
+        pos: SourceSpan,
+    },
+    #[error("the type `{name}` is never used")]
+    #[diagnostic(
+        code(cornucopia::W0003),
+        severity(Warning),
+        help("remove this declaration, or use it in a query or for a composite type")
+    )]
+    UnusedType {
+        #[source_code]
+        src: Arc<NamedSource>,
+        name: String,
+        #[label("declared here")]
+        pos: SourceSpan,
+    },
+    #[error("the parameter `{name}` is inferred as `text` from an untyped literal")]
+    #[diagnostic(
+        code(cornucopia::W0004),
+        severity(Warning),
+        help("cast the parameter or the literal to the intended type, like `:{name}::integer`")
+    )]
+    UntypedParam {
+        #[source_code]
+        src: Arc<NamedSource>,
+        name: String,
+        #[label("inferred as `text`")]
+        pos: SourceSpan,
+    },
+    #[error("the attribute `{name}` is not interpreted by cornucopia")]
+    #[diagnostic(
+        code(cornucopia::W0005),
+        severity(Warning),
+        help("cornucopia only interprets {known}, check the spelling if `{name}` is not read from the `ATTRIBUTES` of the statement")
+    )]
+    UnknownAttribute {
+        #[source_code]
+        src: Arc<NamedSource>,
+        name: String,
+        known: String,
+        #[label("passed through to `ATTRIBUTES`")]
+        pos: SourceSpan,
+    },
+}
+
+impl Warning {
+    #[must_use]
+    pub fn report(&self) -> String {
+        render_report(self)
+    }
+
+    /// Renders this warning as a JSON line, see `--message-format json` for the format.
+    #[must_use]
+    pub fn report_json(&self) -> String {
+        render_json(self)
+    }
+}
@@ -0,0 +1,94 @@
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, SystemTime},
+};
+
+use crate::{error::Error as CornucopiaError, message_format::MessageFormat};
+
+use self::error::Error;
+
+/// Delay between two checks of the watched files.
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Runs `f`, then runs it again each time a file in `paths` changes, until interrupted
+/// with Ctrl-C. Errors returned by `f` are printed using `format` and don't stop the watch.
+pub(crate) fn watch(
+    paths: &[&str],
+    format: MessageFormat,
+    mut f: impl FnMut() -> Result<(), CornucopiaError>,
+) -> Result<(), Error> {
+    // Stop gracefully on Ctrl-C, so that managed databases are cleaned up
+    let interrupted = Arc::new(AtomicBool::new(false));
+    let handler_flag = interrupted.clone();
+    ctrlc::set_handler(move || handler_flag.store(true, Ordering::SeqCst))?;
+
+    let mut last = snapshot(paths);
+    loop {
+        match f() {
+            Ok(()) => format.status("Code generation succeeded."),
+            Err(e) => format.print(e),
+        }
+        format.status("Watching for changes, press Ctrl-C to stop...");
+        loop {
+            std::thread::sleep(POLL_INTERVAL);
+            if interrupted.load(Ordering::SeqCst) {
+                return Ok(());
+            }
+            let current = snapshot(paths);
+            if current != last {
+                last = settle(paths, current);
+                break;
+            }
+        }
+    }
+}
+
+/// Modification time of every file in `paths`, recursing into directories.
+/// Missing files are recorded too, so that their creation is noticed.
+fn snapshot(paths: &[&str]) -> BTreeMap<PathBuf, Option<SystemTime>> {
+    fn visit(path: &Path, snapshot: &mut BTreeMap<PathBuf, Option<SystemTime>>) {
+        if path.is_dir() {
+            for entry in std::fs::read_dir(path).into_iter().flatten().flatten() {
+                visit(&entry.path(), snapshot);
+            }
+        } else {
+            let modified = std::fs::metadata(path).and_then(|m| m.modified()).ok();
+            snapshot.insert(path.to_path_buf(), modified);
+        }
+    }
+
+    let mut snapshot = BTreeMap::new();
+    for path in paths {
+        visit(Path::new(path), &mut snapshot);
+    }
+    snapshot
+}
+
+/// Waits for files to stop changing, as editors often write a file in several steps.
+fn settle(
+    paths: &[&str],
+    mut previous: BTreeMap<PathBuf, Option<SystemTime>>,
+) -> BTreeMap<PathBuf, Option<SystemTime>> {
+    loop {
+        std::thread::sleep(POLL_INTERVAL / 4);
+        let current = snapshot(paths);
+        if current == previous {
+            return current;
+        }
+        previous = current;
+    }
+}
+
+pub(crate) mod error {
+    use miette::Diagnostic;
+    use thiserror::Error as ThisError;
+
+    #[derive(Debug, ThisError, Diagnostic)]
+    #[error("Could not watch for changes: ({0})")]
+    pub struct Error(#[from] pub ctrlc::Error);
+}
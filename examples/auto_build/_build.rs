@@ -1,4 +1,4 @@
-use cornucopia::{CodegenSettings, Error};
+use cornucopia::{CodegenSettings, Error, ManagedBackend};
 
 // This script will generate a new cornucopia file every time your schema or queries change.
 // In this example, we generate the module in our project, but
@@ -11,6 +11,7 @@ fn main() -> Result<(), Error> {
     let settings = CodegenSettings {
         is_async: true,
         derive_ser: false,
+        ..Default::default()
     };
 
     println!("cargo:rerun-if-changed={queries_path}");
@@ -19,7 +20,7 @@ fn main() -> Result<(), Error> {
         queries_path,
         vec![schema_file.into()],
         Some(destination),
-        false,
+        ManagedBackend::default(),
         settings,
     )?;
 
@@ -66,6 +66,16 @@ pub mod queries {
                 Ok(it)
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// SELECT
+        ///     *
+        /// FROM
+        ///     example_table;
+        /// ```
+        ///
+        /// </details>
         pub fn example_query() -> ExampleQueryStmt {
             ExampleQueryStmt(cornucopia_async::private::Stmt::new(
                 "SELECT
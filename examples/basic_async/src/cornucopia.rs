@@ -88,7 +88,7 @@ pub mod types {
                 }
             }
         }
-        #[derive(Debug, postgres_types :: FromSql, Clone, PartialEq)]
+        #[derive(Debug, postgres_types::FromSql, Clone, PartialEq)]
         #[postgres(name = "voiceactor")]
         pub struct Voiceactor {
             pub name: String,
@@ -210,6 +210,14 @@ pub mod queries {
         use cornucopia_async::GenericClient;
         use futures;
         use futures::{StreamExt, TryStreamExt};
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// INSERT INTO Book (title)
+        ///   VALUES (:title);
+        /// ```
+        ///
+        /// </details>
         pub fn insert_book() -> InsertBookStmt {
             InsertBookStmt(cornucopia_async::private::Stmt::new(
                 "INSERT INTO Book (title)
@@ -577,6 +585,16 @@ pub mod queries {
                 Ok(it)
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// SELECT
+        ///     *
+        /// FROM
+        ///     Author;
+        /// ```
+        ///
+        /// </details>
         pub fn authors() -> AuthorsStmt {
             AuthorsStmt(cornucopia_async::private::Stmt::new(
                 "SELECT
@@ -604,6 +622,16 @@ FROM
                 }
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// SELECT
+        ///     Title
+        /// FROM
+        ///     Book;
+        /// ```
+        ///
+        /// </details>
         pub fn books() -> BooksStmt {
             BooksStmt(cornucopia_async::private::Stmt::new(
                 "SELECT
@@ -627,6 +655,18 @@ FROM
                 }
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// SELECT
+        ///     Author.Name
+        /// FROM
+        ///     Author
+        /// WHERE
+        ///     Author.Id = :id;
+        /// ```
+        ///
+        /// </details>
         pub fn author_name_by_id() -> AuthorNameByIdStmt {
             AuthorNameByIdStmt(cornucopia_async::private::Stmt::new(
                 "SELECT
@@ -653,6 +693,23 @@ WHERE
                 }
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// SELECT
+        ///     BookAuthor.AuthorId,
+        ///     Author.Name,
+        ///     BookAuthor.BookId,
+        ///     Book.Title
+        /// FROM
+        ///     BookAuthor
+        ///     INNER JOIN Author ON Author.id = BookAuthor.AuthorId
+        ///     INNER JOIN Book ON Book.Id = BookAuthor.BookId
+        /// WHERE
+        ///     Author.Name LIKE CONCAT(:start_str::text, '%');
+        /// ```
+        ///
+        /// </details>
         pub fn author_name_starting_with() -> AuthorNameStartingWithStmt {
             AuthorNameStartingWithStmt(cornucopia_async::private::Stmt::new(
                 "SELECT
@@ -705,6 +762,18 @@ WHERE
                 self.bind(client, &params.start_str)
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// SELECT
+        ///     voice_actor
+        /// FROM
+        ///     SpongeBobVoiceActor
+        /// WHERE
+        ///     character = :spongebob_character;
+        /// ```
+        ///
+        /// </details>
         pub fn select_voice_actor_with_character() -> SelectVoiceActorWithCharacterStmt {
             SelectVoiceActorWithCharacterStmt(cornucopia_async::private::Stmt::new(
                 "SELECT
@@ -736,6 +805,17 @@ WHERE
                 }
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// SELECT
+        ///     Title,
+        ///     Translations
+        /// FROM
+        ///     Book;
+        /// ```
+        ///
+        /// </details>
         pub fn select_translations() -> SelectTranslationsStmt {
             SelectTranslationsStmt(cornucopia_async::private::Stmt::new(
                 "SELECT
@@ -88,7 +88,7 @@ pub mod types {
                 }
             }
         }
-        #[derive(Debug, postgres_types :: FromSql, Clone, PartialEq)]
+        #[derive(Debug, postgres_types::FromSql, Clone, PartialEq)]
         #[postgres(name = "voiceactor")]
         pub struct Voiceactor {
             pub name: String,
@@ -208,6 +208,14 @@ pub mod types {
 pub mod queries {
     pub mod module_1 {
         use postgres::{fallible_iterator::FallibleIterator, GenericClient};
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// INSERT INTO Book (title)
+        ///   VALUES (:title);
+        /// ```
+        ///
+        /// </details>
         pub fn insert_book() -> InsertBookStmt {
             InsertBookStmt(cornucopia_sync::private::Stmt::new(
                 "INSERT INTO Book (title)
@@ -553,6 +561,16 @@ pub mod queries {
                 Ok(it)
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// SELECT
+        ///     *
+        /// FROM
+        ///     Author;
+        /// ```
+        ///
+        /// </details>
         pub fn authors() -> AuthorsStmt {
             AuthorsStmt(cornucopia_sync::private::Stmt::new(
                 "SELECT
@@ -580,6 +598,16 @@ FROM
                 }
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// SELECT
+        ///     Title
+        /// FROM
+        ///     Book;
+        /// ```
+        ///
+        /// </details>
         pub fn books() -> BooksStmt {
             BooksStmt(cornucopia_sync::private::Stmt::new(
                 "SELECT
@@ -603,6 +631,18 @@ FROM
                 }
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// SELECT
+        ///     Author.Name
+        /// FROM
+        ///     Author
+        /// WHERE
+        ///     Author.Id = :id;
+        /// ```
+        ///
+        /// </details>
         pub fn author_name_by_id() -> AuthorNameByIdStmt {
             AuthorNameByIdStmt(cornucopia_sync::private::Stmt::new(
                 "SELECT
@@ -629,6 +669,23 @@ WHERE
                 }
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// SELECT
+        ///     BookAuthor.AuthorId,
+        ///     Author.Name,
+        ///     BookAuthor.BookId,
+        ///     Book.Title
+        /// FROM
+        ///     BookAuthor
+        ///     INNER JOIN Author ON Author.id = BookAuthor.AuthorId
+        ///     INNER JOIN Book ON Book.Id = BookAuthor.BookId
+        /// WHERE
+        ///     Author.Name LIKE CONCAT(:start_str::text, '%');
+        /// ```
+        ///
+        /// </details>
         pub fn author_name_starting_with() -> AuthorNameStartingWithStmt {
             AuthorNameStartingWithStmt(cornucopia_sync::private::Stmt::new(
                 "SELECT
@@ -681,6 +738,18 @@ WHERE
                 self.bind(client, &params.start_str)
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// SELECT
+        ///     voice_actor
+        /// FROM
+        ///     SpongeBobVoiceActor
+        /// WHERE
+        ///     character = :spongebob_character;
+        /// ```
+        ///
+        /// </details>
         pub fn select_voice_actor_with_character() -> SelectVoiceActorWithCharacterStmt {
             SelectVoiceActorWithCharacterStmt(cornucopia_sync::private::Stmt::new(
                 "SELECT
@@ -712,6 +781,17 @@ WHERE
                 }
             }
         }
+        /// <details><summary>SQL</summary>
+        ///
+        /// ```sql
+        /// SELECT
+        ///     Title,
+        ///     Translations
+        /// FROM
+        ///     Book;
+        /// ```
+        ///
+        /// </details>
         pub fn select_translations() -> SelectTranslationsStmt {
             SelectTranslationsStmt(cornucopia_sync::private::Stmt::new(
                 "SELECT
@@ -0,0 +1,28 @@
+-- Types are declared first
+--: Author(id, name?)
+--: BookParams(author_id?, title?)
+
+--! authors: Author
+SELECT * FROM author;
+--! author_by_id (id): Author #[timeout = "5s", deprecated]
+SELECT *
+  FROM author
+ WHERE id = :id ;
+
+-- Parameters and rows can also be listed inline
+--! insert_book BookParams: (id?)
+INSERT INTO book (title, author_id) VALUES (:title, :author_id) RETURNING id;
+--! everything_null: EverythingNull(
+--   bytea_?,
+--   date_?,
+--   json_?,
+--   jsonb_?,
+--   text_?,
+--   time_?,
+--   timestamp_?,
+--   timestamptz_?,
+--   varchar_?,
+-- )
+SELECT * FROM everything;
+--! multi_line_null: MultiLineNull(a?, b?)
+SELECT * FROM multi;
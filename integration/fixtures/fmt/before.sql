@@ -0,0 +1,23 @@
+  -- Types are declared first
+--:Author(  name? ,id)
+--:   BookParams ( title?,author_id? )
+
+
+--! authors:Author
+SELECT * FROM author;   
+--! author_by_id  ( id )   :Author   #[ timeout="5s" ,deprecated ]
+SELECT *
+  FROM author
+ WHERE id = :id ;   
+
+
+    -- Parameters and rows can also be listed inline
+--! insert_book BookParams :(id?)
+INSERT INTO book (title, author_id) VALUES (:title, :author_id) RETURNING id;
+--! everything_null: EverythingNull(text_?, varchar_?, bytea_?, timestamp_?, timestamptz_?, date_?, time_?, json_?, jsonb_?)
+SELECT * FROM everything;
+--! multi_line_null: MultiLineNull(
+--   b?,
+--   a?
+-- )
+SELECT * FROM multi;
@@ -1,12 +1,16 @@
 use std::{
     borrow::Cow,
+    collections::BTreeMap,
     fmt::Display,
     io::Write,
     process::{Command, ExitCode, Stdio},
 };
 
 use clap::Parser;
-use cornucopia::{container, CodegenSettings, Error};
+use cornucopia::{
+    container::{self, ContainerSettings},
+    CodegenSettings, DeriveSettings, Error, GeneratedCode, TypeDerives, Warning,
+};
 use owo_colors::OwoColorize;
 
 /// Start cornucopia test runner
@@ -34,6 +38,12 @@ struct ErrorTest<'a> {
     schema: Option<&'a str>,
     query_name: Option<&'a str>,
     error: Cow<'a, str>,
+    /// Migrations by file name, loaded from a migrations directory after the schema
+    #[serde(borrow)]
+    migrations: Option<BTreeMap<&'a str, &'a str>>,
+    /// Query files by path, written in the queries directory instead of `query`
+    #[serde(borrow)]
+    queries: Option<BTreeMap<&'a str, &'a str>>,
 }
 
 #[derive(serde::Deserialize)]
@@ -50,9 +60,42 @@ struct CodegenTest<'a> {
     destination: Option<&'a str>,
     sync: Option<bool>,
     derive_ser: Option<bool>,
+    /// Derives and attributes of every row struct and Postgres type
+    #[serde(default)]
+    derives: Vec<String>,
+    #[serde(default)]
+    attributes: Vec<String>,
+    /// Derives and attributes of the types generated for Postgres types
+    #[serde(default)]
+    types: BTreeMap<String, Derives>,
+    /// Derives and attributes of the row and params structs
+    #[serde(default)]
+    structs: BTreeMap<String, Derives>,
     run: Option<Run>,
 }
 
+#[derive(serde::Deserialize)]
+struct Derives {
+    #[serde(default)]
+    derives: Vec<String>,
+    #[serde(default)]
+    attributes: Vec<String>,
+}
+
+impl From<Derives> for TypeDerives {
+    fn from(
+        Derives {
+            derives,
+            attributes,
+        }: Derives,
+    ) -> Self {
+        Self {
+            derives,
+            attributes,
+        }
+    }
+}
+
 #[derive(serde::Deserialize)]
 #[serde(untagged)]
 enum Run {
@@ -84,15 +127,21 @@ fn test(
         apply_codegen,
     }: Args,
 ) -> bool {
-    // Start by removing previous container if it was left open
-    container::cleanup(false).ok();
-    container::setup(false).unwrap();
+    // Codegen tests run code connecting to this port
+    let settings = ContainerSettings {
+        port: Some(5435),
+        ..Default::default()
+    };
+    let container = container::setup(&settings).unwrap();
     let successful = std::panic::catch_unwind(|| {
-        let mut client = cornucopia::conn::cornucopia_conn().unwrap();
+        let mut client = cornucopia::conn::cornucopia_conn(&container).unwrap();
         display(run_errors_test(&mut client, apply_errors)).unwrap()
+            && display(run_fmt_test()).unwrap()
+            && display(run_lsp_test(&mut client)).unwrap()
+            && display(run_dir_output_test(&mut client)).unwrap()
             && display(run_codegen_test(&mut client, apply_codegen)).unwrap()
     });
-    container::cleanup(false).unwrap();
+    container.cleanup().unwrap();
     successful.unwrap()
 }
 
@@ -146,27 +195,46 @@ fn run_errors_test(
                 [SCHEMA_BASE, test.schema.unwrap_or_default()].concat(),
             )?;
 
+            // Generate migrations files
+            let mut schema_files = vec![String::from("schema.sql")];
+            if let Some(migrations) = &test.migrations {
+                for (name, migration) in migrations {
+                    let path = std::path::Path::new("migrations").join(name);
+                    std::fs::create_dir_all(path.parent().unwrap())?;
+                    std::fs::write(path, migration)?;
+                }
+                schema_files.push(String::from("migrations"));
+            }
+
             // Generate queries files
             std::fs::create_dir("queries")?;
-            let name = test.query_name.unwrap_or("test.sql");
-            std::fs::write(&format!("queries/{name}"), test.query.unwrap_or_default())?;
+            if let Some(queries) = &test.queries {
+                for (name, query) in queries {
+                    let path = std::path::Path::new("queries").join(name);
+                    std::fs::create_dir_all(path.parent().unwrap())?;
+                    std::fs::write(path, query)?;
+                }
+            } else {
+                let name = test.query_name.unwrap_or("test.sql");
+                std::fs::write(&format!("queries/{name}"), test.query.unwrap_or_default())?;
+            }
 
             // Run codegen
-            let result: Result<(), cornucopia::Error> = (|| {
-                cornucopia::load_schema(client, vec!["schema.sql".into()])?;
-                cornucopia::generate_live(
-                    client,
-                    "queries",
-                    None,
-                    CodegenSettings {
-                        is_async: false,
-                        derive_ser: false,
-                    },
-                )?;
-                Ok(())
+            let result: Result<GeneratedCode, cornucopia::Error> = (|| {
+                cornucopia::load_schema(client, schema_files)?;
+                cornucopia::generate_live(client, "queries", None, CodegenSettings::default())
             })();
 
-            let err = result.err().map(Error::report).unwrap_or_default();
+            // Warnings are reported when codegen succeeds
+            let err = match result {
+                Ok(generated_code) => generated_code
+                    .warnings()
+                    .iter()
+                    .map(Warning::report)
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+                Err(err) => err.report(),
+            };
             if err.trim() == test.error.trim() {
                 println!("{} {}", test.name, "OK".green());
             } else {
@@ -219,6 +287,22 @@ fn run_codegen_test(
             let destination = codegen_test.destination.unwrap_or("src/cornucopia.rs");
             let is_async = !codegen_test.sync.unwrap_or(false);
             let derive_ser = codegen_test.derive_ser.unwrap_or(false);
+            let derives = DeriveSettings {
+                global: TypeDerives {
+                    derives: codegen_test.derives,
+                    attributes: codegen_test.attributes,
+                },
+                types: codegen_test
+                    .types
+                    .into_iter()
+                    .map(|(k, v)| (k, v.into()))
+                    .collect(),
+                structs: codegen_test
+                    .structs
+                    .into_iter()
+                    .map(|(k, v)| (k, v.into()))
+                    .collect(),
+            };
 
             // Load schema
             reset_db(client)?;
@@ -235,6 +319,7 @@ fn run_codegen_test(
                     CodegenSettings {
                         is_async,
                         derive_ser,
+                        derives,
                     },
                 )
                 .map_err(Error::report)?;
@@ -246,17 +331,30 @@ fn run_codegen_test(
             } else {
                 // Get currently checked-in generate file
                 let old_codegen = std::fs::read_to_string(destination).unwrap_or_default();
-                // Generate new file
-                let new_codegen = cornucopia::generate_live(
+                // Generate new file, keeping its metadata for offline generation
+                let metadata_dir = tempfile::tempdir()?;
+                let metadata = metadata_dir.path().join("metadata.json");
+                let metadata = metadata.to_str().unwrap();
+                let settings = CodegenSettings {
+                    is_async,
+                    derive_ser,
+                    derives,
+                };
+                let new_codegen = cornucopia::generate_live_with_metadata(
                     client,
                     queries_path,
                     None,
-                    CodegenSettings {
-                        is_async,
-                        derive_ser,
-                    },
+                    Some(metadata),
+                    settings.clone(),
                 )
                 .map_err(Error::report)?;
+                // Offline generation must match live generation
+                let offline_codegen =
+                    cornucopia::generate_offline(queries_path, metadata, None, settings)
+                        .map_err(Error::report)?;
+                if offline_codegen != new_codegen {
+                    Err(format!("offline generation of \"{destination}\" differs"))?;
+                }
                 // Format the generated code string by piping to rustfmt
                 let mut rustfmt = Command::new("rustfmt")
                     .args(["--edition", "2021"])
@@ -267,7 +365,7 @@ fn run_codegen_test(
                     .stdin
                     .as_mut()
                     .unwrap()
-                    .write_all(new_codegen.as_bytes())?;
+                    .write_all(new_codegen.to_string().as_bytes())?;
                 let formated_new_codegen =
                     String::from_utf8(rustfmt.wait_with_output()?.stdout).unwrap();
 
@@ -313,6 +411,284 @@ fn run_codegen_test(
     Ok(successful)
 }
 
+/// Run formatting test, return true if all test are successful
+fn run_fmt_test() -> Result<bool, Box<dyn std::error::Error>> {
+    let mut successful = true;
+    println!("{}", "[fmt]".magenta());
+
+    // Formatting the golden `before` file must give the golden `after` file
+    let temp_dir = tempfile::tempdir()?;
+    let queries = temp_dir.path().join("queries");
+    std::fs::create_dir(&queries)?;
+    std::fs::copy("fixtures/fmt/before.sql", queries.join("golden.sql"))?;
+    let queries = queries.to_str().unwrap();
+    cornucopia::format_queries(queries, false, &mut std::io::sink()).map_err(Error::report)?;
+    let expected = std::fs::read_to_string("fixtures/fmt/after.sql")?;
+    let formatted = std::fs::read_to_string(format!("{queries}/golden.sql"))?;
+    if formatted == expected {
+        println!("golden {}", "OK".green());
+    } else {
+        successful = false;
+        println!(
+            "golden {}\n{}\n{}\n{}\n{}",
+            "ERR".red(),
+            "Expected:".bright_black(),
+            expected,
+            "Got:".bright_black(),
+            formatted
+        );
+    }
+    Ok(successful)
+}
+
+const LSP_QUERIES: &str = "--: Author(name?)
+
+--! authors: Author
+SELECT id, name FROM author;
+
+--! author_by_id (id): Author
+SELECT id, name FROM author WHERE id = :id;
+
+--! quoted: (semi)
+SELECT id, ';' AS semi FROM author;
+";
+
+/// Run language server test, return true if all test are successful
+fn run_lsp_test(client: &mut postgres::Client) -> Result<bool, Box<dyn std::error::Error>> {
+    use lsp_server::{Connection, Message, Notification, Request, Response};
+    use serde_json::{json, Value};
+
+    let mut successful = true;
+    println!("{}", "[lsp]".magenta());
+    reset_db(client)?;
+    client.batch_execute(SCHEMA_BASE)?;
+
+    let (server, editor) = Connection::memory();
+    let uri = "file:///queries/authors.sql";
+    // Answers of the server, with the diagnostics it publishes in between
+    let mut next_id = 0;
+    let mut request = |method: &str, params: Value| -> Result<Value, Box<dyn std::error::Error>> {
+        next_id += 1;
+        let request = Request::new(next_id.into(), method.to_string(), params);
+        editor.sender.send(request.into())?;
+        loop {
+            match editor
+                .receiver
+                .recv_timeout(std::time::Duration::from_secs(30))?
+            {
+                Message::Response(Response { id, result, .. }) if id == next_id.into() => {
+                    return Ok(result.unwrap_or_default())
+                }
+                _ => {}
+            }
+        }
+    };
+    let notify = |method: &str, params: Value| {
+        editor
+            .sender
+            .send(Notification::new(method.to_string(), params).into())
+    };
+    let diagnostics = || -> Result<Value, Box<dyn std::error::Error>> {
+        loop {
+            if let Message::Notification(notification) = editor
+                .receiver
+                .recv_timeout(std::time::Duration::from_secs(30))?
+            {
+                if notification.method == "textDocument/publishDiagnostics" {
+                    return Ok(notification.params["diagnostics"].clone());
+                }
+            }
+        }
+    };
+    let mut check = |name: &str, ok: bool, got: &Value| {
+        if ok {
+            println!("{name} {}", "OK".green());
+        } else {
+            successful = false;
+            println!("{name} {}\n{}\n{got}", "ERR".red(), "Got:".bright_black());
+        }
+    };
+    let position = |line: u32, character: u32| {
+        json!({
+            "textDocument": { "uri": uri },
+            "position": { "line": line, "character": character }
+        })
+    };
+
+    std::thread::scope(|scope| -> Result<(), Box<dyn std::error::Error>> {
+        let server =
+            scope.spawn(|| cornucopia::serve_lsp_live(client, server, CodegenSettings::default()));
+
+        request("initialize", json!({ "capabilities": {} }))?;
+        notify("initialized", json!({}))?;
+
+        // Errors are published when a file is opened, and cleared once it is fixed and saved
+        let broken = LSP_QUERIES.replacen("FROM author;", "FROM authors;", 1);
+        notify(
+            "textDocument/didOpen",
+            json!({ "textDocument": { "uri": uri, "languageId": "sql", "version": 1, "text": broken } }),
+        )?;
+        let got = diagnostics()?;
+        let ok = got.as_array().map(Vec::len) == Some(1)
+            && got[0]["range"]["start"]["line"] == 3
+            && got[0]["message"]
+                .as_str()
+                .unwrap_or_default()
+                .contains("authors");
+        check("diagnostics", ok, &got);
+        notify(
+            "textDocument/didChange",
+            json!({ "textDocument": { "uri": uri, "version": 2 }, "contentChanges": [{ "text": LSP_QUERIES }] }),
+        )?;
+        notify(
+            "textDocument/didSave",
+            json!({ "textDocument": { "uri": uri } }),
+        )?;
+        let got = diagnostics()?;
+        check("diagnostics cleared", got == json!([]), &got);
+
+        // Hovering a query shows its generated row
+        let got = request("textDocument/hover", position(2, 5))?;
+        let ok = got["contents"]["value"]
+            .as_str()
+            .unwrap_or_default()
+            .contains("pub struct Author {\n    pub id: i32,\n    pub name: Option<String>,\n}");
+        check("hover", ok, &got);
+
+        // A named row leads to its declaration
+        let got = request("textDocument/definition", position(2, 15))?;
+        let ok = got["uri"] == uri
+            && got["range"]
+                == json!({ "start": { "line": 0, "character": 4 }, "end": { "line": 0, "character": 10 } });
+        check("definition", ok, &got);
+
+        // Columns are completed from the whole query, even past a quoted `;`
+        let editing = LSP_QUERIES.replace("--! quoted: (semi)", "--! quoted: (");
+        notify(
+            "textDocument/didChange",
+            json!({ "textDocument": { "uri": uri, "version": 3 }, "contentChanges": [{ "text": editing }] }),
+        )?;
+        let got = request("textDocument/completion", position(8, 13))?;
+        let labels: Vec<_> = got
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|it| it["label"].as_str())
+            .collect();
+        check("completion", labels == ["id", "semi"], &got);
+
+        request("shutdown", Value::Null)?;
+        notify("exit", Value::Null)?;
+        server.join().unwrap().map_err(Error::report)?;
+        Ok(())
+    })?;
+    Ok(successful)
+}
+
+/// Run directory output test, return true if all test are successful
+fn run_dir_output_test(client: &mut postgres::Client) -> Result<bool, Box<dyn std::error::Error>> {
+    let mut successful = true;
+    println!("{}", "[directory output]".magenta());
+    reset_db(client)?;
+    client.batch_execute(SCHEMA_BASE)?;
+
+    let temp_dir = tempfile::tempdir()?;
+    let path = |path: &str| temp_dir.path().join(path);
+    std::fs::create_dir_all(path("queries/nested"))?;
+    std::fs::write(
+        path("queries/authors.sql"),
+        "--! authors\nSELECT id, name FROM author;",
+    )?;
+    std::fs::write(
+        path("queries/nested/names.sql"),
+        "--! names\nSELECT name FROM author;",
+    )?;
+    std::fs::create_dir(path("out"))?;
+    std::fs::write(path("out/handwritten.rs"), "pub fn kept() {}\n")?;
+    let queries = path("queries");
+    let queries = queries.to_str().unwrap();
+    let out = format!("{}/", path("out").to_str().unwrap());
+    let generate = |client: &mut postgres::Client, destination: &str| {
+        cornucopia::generate_live(
+            client,
+            queries,
+            Some(destination),
+            CodegenSettings::default(),
+        )
+        .map_err(Error::report)
+    };
+    let mut check = |name: &str, ok: bool| {
+        if ok {
+            println!("{name} {}", "OK".green());
+        } else {
+            successful = false;
+            println!("{name} {}", "ERR".red());
+        }
+    };
+    let files = |dir: &str| -> Vec<String> {
+        let mut files = Vec::new();
+        let mut dirs = vec![path(dir)];
+        while let Some(dir) = dirs.pop() {
+            for entry in std::fs::read_dir(dir).into_iter().flatten().flatten() {
+                if entry.path().is_dir() {
+                    dirs.push(entry.path());
+                } else {
+                    let file = entry.path();
+                    let file = file.strip_prefix(path(".")).unwrap_or(&file);
+                    files.push(file.to_string_lossy().replace('\\', "/"));
+                }
+            }
+        }
+        files.sort();
+        files
+    };
+
+    // A trailing `/` makes the destination a directory, with one file per module
+    generate(client, &out)?;
+    check(
+        "files",
+        files("out")
+            == [
+                "out/handwritten.rs",
+                "out/mod.rs",
+                "out/queries/authors.rs",
+                "out/queries/mod.rs",
+                "out/queries/nested/mod.rs",
+                "out/queries/nested/names.rs",
+                "out/types.rs",
+            ],
+    );
+
+    // Files of removed modules are stale, and only generated files are removed
+    std::fs::remove_file(path("queries/nested/names.sql"))?;
+    std::fs::remove_dir(path("queries/nested"))?;
+    let generated = generate(client, &out)?;
+    let is_outdated = cornucopia::check_generated_code(&out, &generated, &mut std::io::sink());
+    check("up to date", is_outdated.is_ok());
+    check(
+        "stale removal",
+        files("out")
+            == [
+                "out/handwritten.rs",
+                "out/mod.rs",
+                "out/queries/authors.rs",
+                "out/queries/mod.rs",
+                "out/types.rs",
+            ],
+    );
+    std::fs::write(path("out/queries/nested.rs"), generated.to_single_file())?;
+    let is_outdated = cornucopia::check_generated_code(&out, &generated, &mut std::io::sink());
+    check("stale detection", is_outdated.is_err());
+
+    // Without a trailing `/`, the destination is a single file, whatever its extension
+    generate(client, path("single").to_str().unwrap())?;
+    check(
+        "single file",
+        std::fs::read_to_string(path("single"))? == generated.to_single_file(),
+    );
+    Ok(successful)
+}
+
 #[cfg(test)]
 mod test {
     use crate::test;